@@ -6,8 +6,10 @@ use std::fs;
 use regex::Regex;
 use lazy_static::lazy_static;
 
+use crate::themes::ThemeManager;
+
 /// Main configuration structure for HyprSupreme-Builder
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     /// Project metadata
     #[serde(default)]
@@ -39,7 +41,7 @@ fn default_profile() -> String {
 }
 
 /// Metadata about the configuration project
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Metadata {
     /// Name of the configuration
     #[serde(default = "default_name")]
@@ -67,7 +69,7 @@ fn default_version() -> String {
 }
 
 /// Profile for different environments (e.g., laptop, desktop, work)
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Profile {
     /// Profile-specific variables that override global ones
     #[serde(default)]
@@ -83,18 +85,39 @@ pub struct Profile {
 }
 
 /// Import or include other configuration files
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Import {
     /// Path to the file to import
     pub path: PathBuf,
-    
-    /// Whether to merge with existing configuration or replace
+
+    /// Deprecated, superseded by `strategy`. Historically toggled between an additive
+    /// merge and a full replace, but a plain `[[imports]]` (this unset) must never wipe
+    /// the base configuration, so it no longer has any effect on the merge outcome --
+    /// use `strategy` to opt into `Replace` explicitly.
     #[serde(default)]
     pub merge: bool,
+
+    /// How the imported file's values should be combined with the base configuration
+    #[serde(default)]
+    pub strategy: Option<ImportStrategy>,
+}
+
+/// Strategy used to combine an imported configuration with the base one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ImportStrategy {
+    /// Recursively merge tables key-by-key; scalars and arrays from the import win
+    Deep,
+
+    /// Discard the base configuration entirely in favor of the imported one
+    Replace,
+
+    /// Like `Deep`, but arrays are concatenated instead of replaced
+    AppendArrays,
 }
 
 /// Hyprland-specific configuration
-#[derive(Debug, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct HyprlandConfig {
     /// Path to main Hyprland configuration file
     pub config_path: Option<PathBuf>,
@@ -117,7 +140,7 @@ pub struct HyprlandConfig {
 }
 
 /// Hyprland module for organization
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HyprlandModule {
     /// Name of the module
     pub name: String,
@@ -135,7 +158,7 @@ fn default_true() -> bool {
 }
 
 /// Keybinding configuration
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Keybinding {
     /// Modifier keys (e.g., SUPER, ALT)
     pub modifiers: Vec<String>,
@@ -152,7 +175,7 @@ pub struct Keybinding {
 }
 
 /// Autostart application configuration
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Autostart {
     /// Command to execute
     pub command: String,
@@ -214,66 +237,38 @@ impl Config {
         
         let imported_config: Config = toml::from_str(&content)
             .with_context(|| format!("Failed to parse import file: {}", full_path.display()))?;
-        
-        // Merge or replace configuration
-        self.merge_config(imported_config, import.merge);
-        
+
+        // Deep is the only safe default: a plain `[[imports]]` with nothing else set
+        // must extend the base configuration, not discard it. `Replace` is destructive
+        // (it drops every other section) and is only used when a `strategy` explicitly
+        // asks for it.
+        let strategy = import.strategy.unwrap_or(ImportStrategy::Deep);
+        self.merge_config(imported_config, strategy)?;
+
         Ok(())
     }
-    
-    /// Merge another configuration into this one
-    fn merge_config(&mut self, other: Config, merge: bool) {
-        // Merge variables
-        for (key, value) in other.variables {
-            if merge && self.variables.contains_key(&key) {
-                continue;
-            }
-            self.variables.insert(key, value);
-        }
-        
-        // Merge profiles
-        for (name, profile) in other.profiles {
-            if merge && self.profiles.contains_key(&name) {
-                // Merge profiles
-                if let Some(existing) = self.profiles.get_mut(&name) {
-                    for (key, value) in profile.variables {
-                        if !existing.variables.contains_key(&key) {
-                            existing.variables.insert(key, value);
-                        }
-                    }
-                    
-                    existing.imports.extend(profile.imports);
-                    
-                    if existing.hyprland.is_none() {
-                        existing.hyprland = profile.hyprland;
-                    }
-                }
-            } else {
-                self.profiles.insert(name, profile);
-            }
-        }
-        
-        // Add imports
-        self.imports.extend(other.imports);
-        
-        // Merge Hyprland config
-        if merge {
-            // Merge modules
-            self.hyprland.modules.extend(other.hyprland.modules);
-            
-            // Merge theme
-            for (key, value) in other.hyprland.theme {
-                if !self.hyprland.theme.contains_key(&key) {
-                    self.hyprland.theme.insert(key, value);
-                }
-            }
-            
-            // Merge keybindings and autostart
-            self.hyprland.keybindings.extend(other.hyprland.keybindings);
-            self.hyprland.autostart.extend(other.hyprland.autostart);
-        } else if self.hyprland.config_path.is_none() {
-            self.hyprland = other.hyprland;
+
+    /// Merge another configuration into this one using the given strategy. Unlike the
+    /// old field-by-field merge, this walks both configs as `toml::Value` trees so a
+    /// nested key like `hyprland.theme.accent` can be overridden without dragging along
+    /// unrelated sibling keys.
+    fn merge_config(&mut self, other: Config, strategy: ImportStrategy) -> Result<()> {
+        if strategy == ImportStrategy::Replace {
+            *self = other;
+            return Ok(());
         }
+
+        let base_value = toml::Value::try_from(&*self)
+            .with_context(|| "Failed to serialize configuration for merging")?;
+        let other_value = toml::Value::try_from(&other)
+            .with_context(|| "Failed to serialize imported configuration for merging")?;
+
+        let merged_value = merge_toml_values(base_value, other_value, strategy);
+
+        *self = merged_value.try_into()
+            .with_context(|| "Failed to deserialize merged configuration")?;
+
+        Ok(())
     }
     
     /// Get the active profile
@@ -282,44 +277,147 @@ impl Config {
         self.profiles.get(name)
             .with_context(|| format!("Profile '{}' not found", name))
     }
-    
-    /// Resolve variables in a string
-    pub fn resolve_variables(&self, input: &str, profile_name: Option<&str>) -> String {
+
+    /// Build a fully resolved snapshot of this configuration: the selected profile's
+    /// variables overlaid on the global ones, its `hyprland` section in place of the
+    /// base one (if set), and every `${...}` reference in that section expanded.
+    /// Used by `config dump` to show exactly what a build would act on.
+    pub fn resolved(&self, profile_name: Option<&str>, theme_manager: Option<&ThemeManager>) -> Result<Config> {
+        let profile = self.get_active_profile(profile_name).ok();
+
+        let mut variables = self.variables.clone();
+        if let Some(profile) = profile {
+            variables.extend(profile.variables.clone());
+        }
+
+        let mut hyprland = profile
+            .and_then(|profile| profile.hyprland.clone())
+            .unwrap_or_else(|| self.hyprland.clone());
+
+        for binding in &mut hyprland.keybindings {
+            binding.command = self.resolve_variables(&binding.command, profile_name, theme_manager)?;
+            if let Some(description) = binding.description.take() {
+                binding.description = Some(self.resolve_variables(&description, profile_name, theme_manager)?);
+            }
+        }
+        for autostart in &mut hyprland.autostart {
+            autostart.command = self.resolve_variables(&autostart.command, profile_name, theme_manager)?;
+        }
+        for value in hyprland.theme.values_mut() {
+            *value = self.resolve_variables(value, profile_name, theme_manager)?;
+        }
+
+        Ok(Config {
+            metadata: self.metadata.clone(),
+            variables,
+            profiles: HashMap::new(),
+            default_profile: profile_name.unwrap_or(&self.default_profile).to_string(),
+            imports: Vec::new(),
+            hyprland,
+        })
+    }
+
+    /// Resolve `${name}` references in a string against profile/global variables and,
+    /// if a `ThemeManager` is given, `${theme.colors.*}`/`${theme.variables.*}` references
+    /// against its active theme. Supports fallbacks (`${name:-default}`) and references
+    /// whose own value contains further `${...}` references, resolved recursively.
+    /// Returns an error naming any reference that is still unresolved and has no default.
+    pub fn resolve_variables(&self, input: &str, profile_name: Option<&str>, theme_manager: Option<&ThemeManager>) -> Result<String> {
         lazy_static! {
-            static ref VAR_REGEX: Regex = Regex::new(r"\$\{([a-zA-Z0-9_.-]+)\}").unwrap();
+            static ref VAR_REGEX: Regex = Regex::new(r"\$\{([a-zA-Z0-9_.-]+)(?::-([^}]*))?\}").unwrap();
         }
-        
+
+        let profile = self.get_active_profile(profile_name).ok();
+        let mut stack = HashSet::new();
+        let mut unresolved = Vec::new();
+
+        let result = self.resolve_variables_in(input, profile, theme_manager, &VAR_REGEX, &mut stack, &mut unresolved);
+
+        if !unresolved.is_empty() {
+            return Err(color_eyre::eyre::eyre!("Unresolved variable reference(s): {}", unresolved.join(", ")));
+        }
+
+        Ok(result)
+    }
+
+    /// Recursively expand `${...}` references in `input`, replacing only the matched
+    /// span for each occurrence so repeated references resolve independently.
+    fn resolve_variables_in(
+        &self,
+        input: &str,
+        profile: Option<&Profile>,
+        theme_manager: Option<&ThemeManager>,
+        var_regex: &Regex,
+        stack: &mut HashSet<String>,
+        unresolved: &mut Vec<String>,
+    ) -> String {
         let mut result = input.to_string();
-        let profile = match self.get_active_profile(profile_name) {
-            Ok(p) => p,
-            Err(_) => return result,
-        };
-        
-        // Keep track of variables we've tried to resolve to avoid infinite recursion
-        let mut visited = HashSet::new();
-        
-        while let Some(captures) = VAR_REGEX.captures(&result) {
-            let full_match = captures.get(0).unwrap().as_str();
-            let var_name = captures.get(1).unwrap().as_str();
-            
-            // Avoid infinite recursion
-            if visited.contains(var_name) {
-                break;
-            }
-            visited.insert(var_name.to_string());
-            
-            // Look up in profile variables first, then global variables
-            let replacement = profile.variables.get(var_name)
-                .or_else(|| self.variables.get(var_name))
-                .map(|s| s.as_str())
-                .unwrap_or("");
-            
-            result = result.replace(full_match, replacement);
+        let mut offset = 0;
+
+        while let Some(captures) = var_regex.captures(&result[offset..]) {
+            let whole_match = captures.get(0).unwrap();
+            let var_name = captures.get(1).unwrap().as_str().to_string();
+            let default_value = captures.get(2).map(|m| m.as_str().to_string());
+            let match_start = offset + whole_match.start();
+            let match_end = offset + whole_match.end();
+
+            let replacement = if stack.contains(&var_name) {
+                unresolved.push(format!("{} (circular reference)", var_name));
+                default_value.unwrap_or_default()
+            } else {
+                stack.insert(var_name.clone());
+                let looked_up = self.lookup_variable(&var_name, profile, theme_manager);
+                let resolved = match looked_up.or_else(|| default_value.clone()) {
+                    Some(value) => self.resolve_variables_in(&value, profile, theme_manager, var_regex, stack, unresolved),
+                    None => {
+                        unresolved.push(var_name.clone());
+                        String::new()
+                    }
+                };
+                stack.remove(&var_name);
+                resolved
+            };
+
+            result.replace_range(match_start..match_end, &replacement);
+            offset = match_start + replacement.len();
         }
-        
+
         result
     }
+
+    /// Look up a single `${...}` reference: `theme.colors.*`/`theme.variables.*` against
+    /// the active theme, everything else against profile then global variables.
+    fn lookup_variable(&self, name: &str, profile: Option<&Profile>, theme_manager: Option<&ThemeManager>) -> Option<String> {
+        if let Some(theme_path) = name.strip_prefix("theme.") {
+            let theme_manager = theme_manager?;
+            if let Some(color_name) = theme_path.strip_prefix("colors.") {
+                return theme_manager.get_theme_color(color_name).ok();
+            }
+            if let Some(var_name) = theme_path.strip_prefix("variables.") {
+                return theme_manager.get_theme_variable(var_name).ok();
+            }
+            return None;
+        }
+
+        profile.and_then(|p| p.variables.get(name))
+            .or_else(|| self.variables.get(name))
+            .cloned()
+    }
     
+    /// Emit this type's JSON Schema as a pretty-printed string, for editor autocompletion
+    pub fn schema() -> String {
+        serde_json::to_string_pretty(&crate::validate::config_schema()).unwrap_or_default()
+    }
+
+    /// Parse and validate a config file, collecting every unknown key and missing
+    /// required field rather than failing on the first one the way `Config::from_file`
+    /// does; of type mismatches, only the first is reported, and only when no other
+    /// problem was found. In `strict` mode, unrecognized keys are reported as errors
+    /// rather than silently tolerated.
+    pub fn validate_file<P: AsRef<Path>>(path: P, strict: bool) -> Result<crate::validate::ValidationReport> {
+        crate::validate::validate_config_file(path, strict)
+    }
+
     /// Create a default configuration
     pub fn default_config() -> Self {
         let mut config = Config {
@@ -359,3 +457,30 @@ impl Config {
         config
     }
 }
+
+/// Recursively merge two `toml::Value` trees, modeled on Helix's `merge_toml_values`:
+/// tables recurse key-by-key, arrays are appended or replaced depending on `strategy`,
+/// and for any other pairing the later (imported) value wins.
+fn merge_toml_values(base: toml::Value, other: toml::Value, strategy: ImportStrategy) -> toml::Value {
+    match (base, other) {
+        (toml::Value::Table(mut base_table), toml::Value::Table(other_table)) => {
+            for (key, other_value) in other_table {
+                let merged_value = match base_table.remove(&key) {
+                    Some(base_value) => merge_toml_values(base_value, other_value, strategy),
+                    None => other_value,
+                };
+                base_table.insert(key, merged_value);
+            }
+            toml::Value::Table(base_table)
+        },
+        (toml::Value::Array(mut base_array), toml::Value::Array(other_array)) => {
+            if strategy == ImportStrategy::AppendArrays {
+                base_array.extend(other_array);
+                toml::Value::Array(base_array)
+            } else {
+                toml::Value::Array(other_array)
+            }
+        },
+        (_, other) => other,
+    }
+}
@@ -0,0 +1,184 @@
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+
+use crate::plugins::PluginError;
+use crate::themes::ThemeError;
+
+/// A named plugin could not be found in the registry
+#[pyclass(extends = PyException)]
+pub struct PluginNotFoundError {
+    #[pyo3(get)]
+    pub plugin_name: String,
+}
+
+#[pymethods]
+impl PluginNotFoundError {
+    #[new]
+    fn new(plugin_name: String) -> Self {
+        PluginNotFoundError { plugin_name }
+    }
+
+    fn __str__(&self) -> String {
+        format!("Plugin not found: {}", self.plugin_name)
+    }
+}
+
+/// A discovered plugin manifest failed validation (bad semver version, duplicate name)
+#[pyclass(extends = PyException)]
+pub struct PluginValidationError {
+    #[pyo3(get)]
+    pub plugin_name: String,
+
+    #[pyo3(get)]
+    pub detail: String,
+}
+
+#[pymethods]
+impl PluginValidationError {
+    #[new]
+    fn new(plugin_name: String, detail: String) -> Self {
+        PluginValidationError { plugin_name, detail }
+    }
+
+    fn __str__(&self) -> String {
+        format!("Plugin '{}' failed validation: {}", self.plugin_name, self.detail)
+    }
+}
+
+/// A plugin hook or command failed while running
+#[pyclass(extends = PyException)]
+pub struct PluginExecutionError {
+    #[pyo3(get)]
+    pub plugin_name: String,
+
+    #[pyo3(get)]
+    pub detail: String,
+}
+
+#[pymethods]
+impl PluginExecutionError {
+    #[new]
+    fn new(plugin_name: String, detail: String) -> Self {
+        PluginExecutionError { plugin_name, detail }
+    }
+
+    fn __str__(&self) -> String {
+        format!("Plugin '{}' failed: {}", self.plugin_name, self.detail)
+    }
+}
+
+/// A named theme could not be found by the loader
+#[pyclass(extends = PyException)]
+pub struct ThemeNotFoundError {
+    #[pyo3(get)]
+    pub theme_name: String,
+}
+
+#[pymethods]
+impl ThemeNotFoundError {
+    #[new]
+    fn new(theme_name: String) -> Self {
+        ThemeNotFoundError { theme_name }
+    }
+
+    fn __str__(&self) -> String {
+        format!("Theme not found: {}", self.theme_name)
+    }
+}
+
+/// A color or variable key was missing from the active theme
+#[pyclass(extends = PyException)]
+pub struct ThemeColorError {
+    #[pyo3(get)]
+    pub color_key: String,
+}
+
+#[pymethods]
+impl ThemeColorError {
+    #[new]
+    fn new(color_key: String) -> Self {
+        ThemeColorError { color_key }
+    }
+
+    fn __str__(&self) -> String {
+        format!("Theme color/variable not found: {}", self.color_key)
+    }
+}
+
+/// Generated configuration directives from two or more sources conflicted
+#[pyclass(extends = PyException)]
+pub struct ConfigConflictError {
+    #[pyo3(get)]
+    pub detail: String,
+}
+
+#[pymethods]
+impl ConfigConflictError {
+    #[new]
+    fn new(detail: String) -> Self {
+        ConfigConflictError { detail }
+    }
+
+    fn __str__(&self) -> String {
+        format!("Config conflict: {}", self.detail)
+    }
+}
+
+/// Map a plugin-subsystem error to the Python exception that best describes it, matching
+/// on the typed `PluginError` variant rather than sniffing the formatted message. Variants
+/// that name a specific plugin (as opposed to `plugin_name`, the plugin the caller was
+/// originally operating on) are attributed to that plugin instead.
+pub fn plugin_error(plugin_name: &str, err: color_eyre::Report) -> PyErr {
+    match err.downcast_ref::<PluginError>() {
+        Some(PluginError::NotFound(name)) => PyErr::new::<PluginNotFoundError, _>((name.clone(),)),
+        Some(typed @ PluginError::MissingDependency { plugin, .. }) => {
+            PyErr::new::<PluginExecutionError, _>((plugin.clone(), typed.to_string()))
+        }
+        Some(typed @ PluginError::VersionMismatch { plugin, .. }) => {
+            PyErr::new::<PluginExecutionError, _>((plugin.clone(), typed.to_string()))
+        }
+        Some(typed @ PluginError::InUseBy { plugin, .. }) => {
+            PyErr::new::<PluginExecutionError, _>((plugin.clone(), typed.to_string()))
+        }
+        Some(typed @ PluginError::ProtocolIncompatible { plugin, .. }) => {
+            PyErr::new::<PluginExecutionError, _>((plugin.clone(), typed.to_string()))
+        }
+        Some(typed @ PluginError::Untrusted { plugin, .. }) => {
+            PyErr::new::<PluginExecutionError, _>((plugin.clone(), typed.to_string()))
+        }
+        Some(typed @ PluginError::DependencyCycle(_)) => {
+            PyErr::new::<PluginExecutionError, _>((plugin_name.to_string(), typed.to_string()))
+        }
+        Some(typed @ PluginError::CapabilityConflict { .. }) => {
+            PyErr::new::<PluginExecutionError, _>((plugin_name.to_string(), typed.to_string()))
+        }
+        None => PyErr::new::<PluginExecutionError, _>((plugin_name.to_string(), err.to_string())),
+    }
+}
+
+/// Map a theme-lookup error (by theme name) to the Python exception that best describes it,
+/// matching on the typed `ThemeError` variant rather than sniffing the formatted message.
+pub fn theme_error(theme_name: &str, err: color_eyre::Report) -> PyErr {
+    match err.downcast_ref::<ThemeError>() {
+        Some(ThemeError::NotFound(name)) => PyErr::new::<ThemeNotFoundError, _>((name.clone(),)),
+        Some(ThemeError::FamilyNotFound(name)) => PyErr::new::<ThemeNotFoundError, _>((name.clone(),)),
+        Some(typed @ ThemeError::VariantNotFound { .. }) => {
+            pyo3::exceptions::PyRuntimeError::new_err(typed.to_string())
+        }
+        None => pyo3::exceptions::PyRuntimeError::new_err(format!("{}: {}", theme_name, err)),
+    }
+}
+
+/// Map a theme color/variable lookup error to `ThemeColorError`
+pub fn theme_color_error(color_key: &str) -> PyErr {
+    PyErr::new::<ThemeColorError, _>((color_key.to_string(),))
+}
+
+/// Map a plugin discovery/validation failure (bad semver, duplicate name) to
+/// `PluginValidationError`, pulling the offending plugin name out of the message
+/// where it's quoted.
+pub fn plugin_validation_error(err: color_eyre::Report) -> PyErr {
+    let message = err.to_string();
+    let plugin_name = message.split('\'').nth(1).unwrap_or("").to_string();
+    PyErr::new::<PluginValidationError, _>((plugin_name, message))
+}
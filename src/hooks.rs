@@ -0,0 +1,212 @@
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use color_eyre::{eyre::Context, Result};
+
+use crate::plugins::{Plugin, PluginManager, PluginRuntime};
+use crate::xdg::Dirs;
+
+/// A point in the build/theme pipeline at which enabled plugins' hooks are run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lifecycle {
+    PreBuild,
+    PostBuild,
+    ThemeApply,
+}
+
+impl Lifecycle {
+    /// The hook name plugins declare in their manifest to run at this point
+    pub fn hook_name(self) -> &'static str {
+        match self {
+            Lifecycle::PreBuild => "pre-build",
+            Lifecycle::PostBuild => "post-build",
+            Lifecycle::ThemeApply => "theme-apply",
+        }
+    }
+}
+
+/// Outcome of running a single plugin's hook or command, for callers that want to
+/// report on (or point a user at the log of) a pipeline run
+#[derive(Debug)]
+pub struct HookInvocation {
+    /// Plugin the hook or command belongs to
+    pub plugin: String,
+
+    /// Name of the hook or command that ran
+    pub name: String,
+
+    /// Log file the invocation's stdout/stderr were written to
+    pub log_path: PathBuf,
+
+    /// Whether the invocation exited successfully
+    pub success: bool,
+}
+
+/// Run every enabled plugin's `lifecycle` hook, in dependency order, streaming each
+/// invocation's output into its own log file under the cache directory and printing a
+/// concise success/failure line as it goes. Stops at the first failure -- reporting the
+/// partially-written log -- unless `keep_going` is set, in which case every hook still
+/// runs and failures are only surfaced as an error once the whole pass is done.
+pub fn run_lifecycle(
+    plugin_manager: &PluginManager,
+    lifecycle: Lifecycle,
+    args: &[&str],
+    keep_going: bool,
+) -> Result<Vec<HookInvocation>> {
+    let hook_name = lifecycle.hook_name();
+    let order = plugin_manager
+        .enabled_plugins_in_dependency_order()
+        .with_context(|| "Failed to order plugins for hook execution")?;
+
+    let log_dir = hook_log_dir()?;
+
+    let mut invocations = Vec::new();
+    let mut failure = None;
+
+    for plugin_name in order {
+        let Some(plugin) = plugin_manager.get_plugin(&plugin_name) else {
+            continue;
+        };
+        let Some(hook) = plugin.manifest.hooks.iter().find(|h| h.name == hook_name) else {
+            continue;
+        };
+
+        let invocation = run_invocation(plugin, hook_name, &hook.script, args, &log_dir)?;
+        report_invocation(&invocation);
+
+        let succeeded = invocation.success;
+        invocations.push(invocation);
+
+        if !succeeded && !keep_going {
+            let last = invocations.last().unwrap();
+            failure = Some(color_eyre::eyre::eyre!(
+                "Hook '{}' failed for plugin '{}'; see log: {:?}",
+                hook_name,
+                last.plugin,
+                last.log_path,
+            ));
+            break;
+        }
+    }
+
+    if keep_going {
+        let failed: Vec<String> = invocations.iter()
+            .filter(|invocation| !invocation.success)
+            .map(|invocation| format!("'{}' (log: {:?})", invocation.plugin, invocation.log_path))
+            .collect();
+        if !failed.is_empty() {
+            failure = Some(color_eyre::eyre::eyre!(
+                "Hook '{}' failed for plugin(s): {}",
+                hook_name,
+                failed.join(", "),
+            ));
+        }
+    }
+
+    match failure {
+        Some(err) => Err(err),
+        None => Ok(invocations),
+    }
+}
+
+/// Run a single declared command of `plugin` by name, for manual invocation via
+/// `plugin run <name> <command>`. Unlike lifecycle hooks this runs regardless of the
+/// plugin's enabled state and simply reports its outcome to the caller.
+pub fn run_command(plugin: &Plugin, command_name: &str, args: &[&str]) -> Result<HookInvocation> {
+    let command = plugin
+        .manifest
+        .commands
+        .iter()
+        .find(|c| c.name == command_name)
+        .ok_or_else(|| color_eyre::eyre::eyre!("Command not found: {}", command_name))?;
+
+    let log_dir = hook_log_dir()?;
+    let invocation = run_invocation(plugin, command_name, &command.script, args, &log_dir)?;
+    report_invocation(&invocation);
+
+    Ok(invocation)
+}
+
+/// Directory per-invocation hook/command logs are written under, created on first use
+fn hook_log_dir() -> Result<PathBuf> {
+    let log_dir = Dirs::resolve().cache_dir.join("hooks");
+    fs::create_dir_all(&log_dir)
+        .with_context(|| format!("Failed to create hook log directory: {:?}", log_dir))?;
+    Ok(log_dir)
+}
+
+fn report_invocation(invocation: &HookInvocation) {
+    println!(
+        "{} {}:{} ({})",
+        if invocation.success { "ok  " } else { "FAIL" },
+        invocation.plugin,
+        invocation.name,
+        invocation.log_path.display(),
+    );
+}
+
+/// Spawn `script` (resolved relative to `plugin`'s directory), streaming its stdout and
+/// stderr directly into a fresh log file under `log_dir` named after the plugin and the
+/// hook/command. WASM-backed plugins have no subprocess to stream, so their (buffered)
+/// output is written to the log file in one shot instead. `PluginRuntime::Daemon`
+/// plugins are not routed to their JSON-RPC child here: their `script` is spawned as a
+/// file, the same as for script plugins.
+fn run_invocation(
+    plugin: &Plugin,
+    name: &str,
+    script: &str,
+    args: &[&str],
+    log_dir: &Path,
+) -> Result<HookInvocation> {
+    let log_path = log_dir.join(format!("{}-{}.log", plugin.manifest.name, name));
+
+    let success = if let PluginRuntime::Wasm { .. } = &plugin.manifest.runtime {
+        match plugin
+            .execute_hook(name, args)
+            .or_else(|_| plugin.execute_command(name, args))
+        {
+            Ok(output) => {
+                fs::write(&log_path, output)
+                    .with_context(|| format!("Failed to write hook log: {:?}", log_path))?;
+                true
+            }
+            Err(err) => {
+                fs::write(&log_path, err.to_string())
+                    .with_context(|| format!("Failed to write hook log: {:?}", log_path))?;
+                false
+            }
+        }
+    } else {
+        let script_path = plugin.directory.join(script);
+        if !script_path.exists() {
+            return Err(color_eyre::eyre::eyre!(
+                "Hook script not found: {}",
+                script_path.display()
+            ));
+        }
+
+        let log_file = File::create(&log_path)
+            .with_context(|| format!("Failed to create hook log: {:?}", log_path))?;
+        let stderr_file = log_file
+            .try_clone()
+            .with_context(|| format!("Failed to duplicate hook log handle: {:?}", log_path))?;
+
+        let status = Command::new(&script_path)
+            .args(args)
+            .current_dir(&plugin.directory)
+            .stdout(log_file)
+            .stderr(stderr_file)
+            .status()
+            .with_context(|| format!("Failed to execute hook script: {}", script_path.display()))?;
+
+        status.success()
+    };
+
+    Ok(HookInvocation {
+        plugin: plugin.manifest.name.clone(),
+        name: name.to_string(),
+        log_path,
+        success,
+    })
+}
@@ -0,0 +1,325 @@
+use color_eyre::{eyre::Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::plugins::{PluginHook, PluginManager, WasmModulePlugin};
+use crate::themes::Theme;
+
+/// The kind of disagreement found between two Hyprland configs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictKind {
+    /// The same keyword (possibly nested, e.g. `general.gaps_in`) is assigned
+    /// different values in each config
+    ScalarOverride,
+
+    /// Two `bind*` directives share the same `(modmask, key)` but dispatch differently
+    KeybindCollision,
+
+    /// The same program is started via `exec`/`exec-once` with a different command line
+    DuplicateExec,
+}
+
+/// A single point of disagreement between two configs, ready to hand to a merge UI
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigConflict {
+    /// The normalized key the conflict was found at (dotted keyword path, `MOD+KEY`
+    /// combo, or exec program name)
+    pub key: String,
+
+    /// The value asserted by the first config
+    pub value_a: String,
+
+    /// The value asserted by the second config
+    pub value_b: String,
+
+    /// What kind of conflict this is
+    pub kind: ConflictKind,
+}
+
+/// Directives extracted from one Hyprland config, keyed for conflict comparison
+#[derive(Debug, Default)]
+struct ParsedConfig {
+    /// Dotted keyword path (e.g. `general.gaps_in`) to assigned value
+    keywords: HashMap<String, String>,
+
+    /// `(modmask, key)` to dispatcher (+ args) string
+    binds: HashMap<(String, String), String>,
+
+    /// Program name to the full `exec`/`exec-once` command line that starts it
+    execs: HashMap<String, String>,
+}
+
+/// Parse a Hyprland config into keyword/bind/exec directives, flattening `section { ... }`
+/// blocks into dotted keys (`general { gaps_in = 5 }` becomes `general.gaps_in`).
+fn parse(config: &str) -> ParsedConfig {
+    let mut parsed = ParsedConfig::default();
+    let mut section_stack: Vec<String> = Vec::new();
+
+    for raw_line in config.lines() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(section) = line.strip_suffix('{') {
+            let section = section.trim();
+            if !section.is_empty() {
+                section_stack.push(section.to_string());
+            }
+            continue;
+        }
+
+        if line == "}" {
+            section_stack.pop();
+            continue;
+        }
+
+        let Some((raw_key, raw_value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = raw_key.trim();
+        let value = raw_value.trim();
+
+        if key == "exec" || key == "exec-once" {
+            let program = value.split_whitespace().next().unwrap_or(value);
+            parsed.execs.insert(program.to_string(), value.to_string());
+        } else if key.starts_with("bind") {
+            let parts: Vec<&str> = value.splitn(3, ',').map(|p| p.trim()).collect();
+            if parts.len() == 3 {
+                let modmask = normalize_modmask(parts[0]);
+                let bind_key = parts[1].to_uppercase();
+                let dispatcher = parts[2].to_string();
+                parsed.binds.insert((modmask, bind_key), dispatcher);
+            }
+        } else {
+            let dotted_key = if section_stack.is_empty() {
+                key.to_string()
+            } else {
+                format!("{}.{}", section_stack.join("."), key)
+            };
+            parsed.keywords.insert(dotted_key, value.to_string());
+        }
+    }
+
+    parsed
+}
+
+/// Normalize a bind modmask (e.g. `"SUPER SHIFT"`, `"shift+super"`) so that equivalent
+/// combinations compare equal regardless of order or case
+fn normalize_modmask(raw: &str) -> String {
+    let mut mods: Vec<String> = raw
+        .split(|c| c == ' ' || c == '+')
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_uppercase())
+        .collect();
+    mods.sort();
+    mods.join("+")
+}
+
+/// Compare two Hyprland config strings and report every point where they disagree.
+/// Keywords, binds, and execs that are absent from one side, or that agree on both
+/// sides, are not reported — only genuine conflicts are.
+pub fn detect_conflicts(config_a: &str, config_b: &str) -> Vec<ConfigConflict> {
+    let a = parse(config_a);
+    let b = parse(config_b);
+    let mut conflicts = Vec::new();
+
+    for (key, value_a) in &a.keywords {
+        if let Some(value_b) = b.keywords.get(key) {
+            if value_a != value_b {
+                conflicts.push(ConfigConflict {
+                    key: key.clone(),
+                    value_a: value_a.clone(),
+                    value_b: value_b.clone(),
+                    kind: ConflictKind::ScalarOverride,
+                });
+            }
+        }
+    }
+
+    for ((modmask, key), dispatcher_a) in &a.binds {
+        if let Some(dispatcher_b) = b.binds.get(&(modmask.clone(), key.clone())) {
+            if dispatcher_a != dispatcher_b {
+                conflicts.push(ConfigConflict {
+                    key: format!("{}+{}", modmask, key),
+                    value_a: dispatcher_a.clone(),
+                    value_b: dispatcher_b.clone(),
+                    kind: ConflictKind::KeybindCollision,
+                });
+            }
+        }
+    }
+
+    for (program, line_a) in &a.execs {
+        if let Some(line_b) = b.execs.get(program) {
+            if line_a != line_b {
+                conflicts.push(ConfigConflict {
+                    key: program.clone(),
+                    value_a: line_a.clone(),
+                    value_b: line_b.clone(),
+                    kind: ConflictKind::DuplicateExec,
+                });
+            }
+        }
+    }
+
+    conflicts.sort_by(|x, y| x.key.cmp(&y.key));
+    conflicts
+}
+
+/// Replace `$theme.<token>` placeholders with the matching entry from the theme's
+/// colors (checked first) or variables; a token matching neither is left untouched.
+fn substitute_theme_tokens(input: &str, theme: &Theme) -> String {
+    lazy_static! {
+        static ref THEME_TOKEN: Regex = Regex::new(r"\$theme\.([a-zA-Z0-9_.-]+)").unwrap();
+    }
+
+    THEME_TOKEN.replace_all(input, |caps: &regex::Captures| {
+        let token = &caps[1];
+        theme.get_color(token)
+            .or_else(|| theme.get_variable(token))
+            .cloned()
+            .unwrap_or_else(|| caps[0].to_string())
+    }).into_owned()
+}
+
+/// Self-describing `.wasm` modules (the `WasmModulePlugin` backend) aren't registered
+/// as filters the way directory+manifest plugins are, so their `config_fragment` hook
+/// (if their `plugin_info` declares one) has to be found and invoked directly here.
+/// Returns the modules that declare the hook, in priority order.
+fn wasm_config_fragment_hooks(plugin_manager: &PluginManager) -> Vec<(PathBuf, String, PluginHook)> {
+    let mut hooks: Vec<(PathBuf, String, PluginHook)> = plugin_manager.wasm_plugins().iter()
+        .filter_map(|module| match module {
+            WasmModulePlugin::Initialized { path, info, .. } => info.hooks.iter()
+                .find(|hook| hook.name == "config_fragment")
+                .map(|hook| (path.clone(), info.name.clone(), hook.clone())),
+            WasmModulePlugin::Failed { .. } => None,
+        })
+        .collect();
+
+    hooks.sort_by_key(|(_, _, hook)| hook.priority);
+    hooks
+}
+
+/// Directory+manifest plugins declare a `config_fragment` hook the same way they declare
+/// any other hook, but nothing registers it as a filter the way `register_filter` expects
+/// -- unlike WASM modules (handled separately by `wasm_config_fragment_hooks`), they never
+/// participate in `apply_filters_steps_named` unless something does that registration. Do
+/// it here, once per generation, for every currently-enabled plugin that declares the hook.
+fn register_manifest_config_fragment_hooks(plugin_manager: &mut PluginManager) -> Result<()> {
+    let registrations: Vec<(String, i32)> = plugin_manager.get_enabled_plugins().iter()
+        .filter_map(|plugin| plugin.manifest.hooks.iter()
+            .find(|hook| hook.name == "config_fragment")
+            .map(|hook| (plugin.manifest.name.clone(), hook.priority)))
+        .collect();
+
+    for (plugin_name, priority) in registrations {
+        plugin_manager.register_filter("config_fragment", &plugin_name, priority)?;
+    }
+
+    Ok(())
+}
+
+/// Assemble a full Hyprland config: substitute `$theme.<token>` placeholders against
+/// `theme`'s style maps, then run the `config_fragment` filter hook so plugins can emit
+/// or mutate config sections: first every directory+manifest plugin enabled on
+/// `plugin_manager`, in priority order, then every loaded self-describing WASM module
+/// that declares the hook, in priority order. A plugin overriding a base
+/// keyword is expected and not a conflict; only two *different* plugins contributing
+/// disagreeing directives is, so generation fails with those conflicts serialized as
+/// JSON rather than silently producing a broken config.
+pub fn generate_config(theme: &Theme, plugin_manager: &mut PluginManager, base_config: &str) -> Result<String> {
+    let substituted = substitute_theme_tokens(base_config, theme);
+    register_manifest_config_fragment_hooks(plugin_manager)?;
+    let mut steps = plugin_manager.apply_filters_steps_named("config_fragment", &substituted, "")?;
+
+    let mut current = steps.last().map(|(_, value)| value.clone()).unwrap_or_else(|| substituted.clone());
+    for (path, name, hook) in wasm_config_fragment_hooks(plugin_manager) {
+        match plugin_manager.execute_wasm_module(&path, &hook.script, &current) {
+            Ok(output) => {
+                current = output.clone();
+                steps.push((name, output));
+            },
+            Err(err) => {
+                tracing::warn!("WASM config_fragment hook failed for module {}: {}", name, err);
+            }
+        }
+    }
+
+    let mut conflicts = Vec::new();
+    let mut keyword_owners: HashMap<String, (String, String)> = HashMap::new();
+    let mut bind_owners: HashMap<(String, String), (String, String)> = HashMap::new();
+    let mut exec_owners: HashMap<String, (String, String)> = HashMap::new();
+
+    let mut previous = substituted.clone();
+    for (plugin_name, step) in &steps {
+        let before = parse(&previous);
+        let after = parse(step);
+
+        for (key, value) in &after.keywords {
+            if before.keywords.get(key) == Some(value) {
+                continue;
+            }
+            if let Some((owner, owner_value)) = keyword_owners.get(key) {
+                if owner != plugin_name && owner_value != value {
+                    conflicts.push(ConfigConflict {
+                        key: key.clone(),
+                        value_a: owner_value.clone(),
+                        value_b: value.clone(),
+                        kind: ConflictKind::ScalarOverride,
+                    });
+                }
+            }
+            keyword_owners.insert(key.clone(), (plugin_name.clone(), value.clone()));
+        }
+
+        for (bind_key, dispatcher) in &after.binds {
+            if before.binds.get(bind_key) == Some(dispatcher) {
+                continue;
+            }
+            if let Some((owner, owner_dispatcher)) = bind_owners.get(bind_key) {
+                if owner != plugin_name && owner_dispatcher != dispatcher {
+                    conflicts.push(ConfigConflict {
+                        key: format!("{}+{}", bind_key.0, bind_key.1),
+                        value_a: owner_dispatcher.clone(),
+                        value_b: dispatcher.clone(),
+                        kind: ConflictKind::KeybindCollision,
+                    });
+                }
+            }
+            bind_owners.insert(bind_key.clone(), (plugin_name.clone(), dispatcher.clone()));
+        }
+
+        for (program, line) in &after.execs {
+            if before.execs.get(program) == Some(line) {
+                continue;
+            }
+            if let Some((owner, owner_line)) = exec_owners.get(program) {
+                if owner != plugin_name && owner_line != line {
+                    conflicts.push(ConfigConflict {
+                        key: program.clone(),
+                        value_a: owner_line.clone(),
+                        value_b: line.clone(),
+                        kind: ConflictKind::DuplicateExec,
+                    });
+                }
+            }
+            exec_owners.insert(program.clone(), (plugin_name.clone(), line.clone()));
+        }
+
+        previous = step.clone();
+    }
+
+    if !conflicts.is_empty() {
+        conflicts.sort_by(|x, y| x.key.cmp(&y.key));
+        let conflicts_json = serde_json::to_string(&conflicts)
+            .with_context(|| "Failed to serialize config conflicts")?;
+        return Err(color_eyre::eyre::eyre!(conflicts_json));
+    }
+
+    Ok(steps.into_iter().map(|(_, step)| step).last().unwrap_or(substituted))
+}
@@ -1,9 +1,21 @@
 use pyo3::prelude::*;
-use pyo3::exceptions::PyRuntimeError;
+use pyo3::types::PyDict;
+use std::path::PathBuf;
 
+mod config;
+mod exceptions;
+mod hooks;
+mod hyprconf;
 mod plugins;
 mod themes;
-
+mod validate;
+mod xdg;
+
+use exceptions::{
+    plugin_error, plugin_validation_error, theme_color_error, theme_error, ConfigConflictError,
+    PluginExecutionError, PluginNotFoundError, PluginValidationError, ThemeColorError,
+    ThemeNotFoundError,
+};
 use plugins::PluginManager as RustPluginManager;
 use themes::ThemeManager as RustThemeManager;
 
@@ -24,31 +36,37 @@ impl PluginManager {
 
     fn enable_plugin(&mut self, name: &str) -> PyResult<()> {
         self.inner.enable_plugin(name)
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to enable plugin: {}", e)))
+            .map_err(|e| plugin_error(name, e))
     }
 
     fn disable_plugin(&mut self, name: &str) -> PyResult<()> {
         self.inner.disable_plugin(name)
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to disable plugin: {}", e)))
+            .map_err(|e| plugin_error(name, e))
     }
 
-    fn execute_command(&self, plugin_name: &str, command_name: &str, args: Vec<String>) -> PyResult<String> {
+    fn execute_command(&mut self, plugin_name: &str, command_name: &str, args: Vec<String>) -> PyResult<String> {
         let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
         self.inner.execute_command(plugin_name, command_name, &args_refs)
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to execute command: {}", e)))
+            .map_err(|e| plugin_error(plugin_name, e))
     }
 
-    fn execute_hook(&self, hook_name: &str, context: &str) -> PyResult<String> {
+    fn execute_hook(&mut self, hook_name: &str, context: &str) -> PyResult<String> {
         let results = self.inner.execute_hook(hook_name, &[context])
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to execute hook: {}", e)))?;
-        
+            .map_err(|e| plugin_error(hook_name, e))?;
+
         // Convert HashMap to JSON string for Python consumption
         let json_result = serde_json::to_string(&results)
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize results: {}", e)))?;
-        
+            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to serialize results: {}", e)))?;
+
         Ok(json_result)
     }
 
+    fn discover_plugins(&mut self, paths: Vec<String>) -> PyResult<Vec<String>> {
+        let paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
+        self.inner.discover_plugins(&paths)
+            .map_err(plugin_validation_error)
+    }
+
     fn get_plugins(&self) -> PyResult<Vec<String>> {
         Ok(self.inner.get_plugins())
     }
@@ -62,13 +80,65 @@ impl PluginManager {
                 "description": plugin.manifest.description,
                 "author": plugin.manifest.author
             });
-            
+
             serde_json::to_string(&plugin_info)
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize plugin info: {}", e)))
+                .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to serialize plugin info: {}", e)))
         } else {
-            Err(PyRuntimeError::new_err(format!("Plugin not found: {}", name)))
+            Err(PyErr::new::<PluginNotFoundError, _>((name.to_string(),)))
         }
     }
+
+    #[pyo3(signature = (name, plugin, priority = 0))]
+    fn register_action(&mut self, name: &str, plugin: &str, priority: i32) -> PyResult<()> {
+        self.inner.register_action(name, plugin, priority)
+            .map_err(|e| plugin_error(plugin, e))
+    }
+
+    #[pyo3(signature = (name, plugin, priority = 0))]
+    fn register_filter(&mut self, name: &str, plugin: &str, priority: i32) -> PyResult<()> {
+        self.inner.register_filter(name, plugin, priority)
+            .map_err(|e| plugin_error(plugin, e))
+    }
+
+    #[pyo3(signature = (name, firstresult = false, **context))]
+    fn fire_action(&mut self, name: &str, firstresult: bool, context: Option<&PyDict>) -> PyResult<Vec<String>> {
+        let context_json = dict_to_json(context)?;
+        self.inner.fire_action(name, &context_json, firstresult)
+            .map_err(|e| plugin_error(name, e))
+    }
+
+    #[pyo3(signature = (name, value, **context))]
+    fn apply_filters(&mut self, name: &str, value: &str, context: Option<&PyDict>) -> PyResult<String> {
+        let context_json = dict_to_json(context)?;
+        self.inner.apply_filters(name, value, &context_json)
+            .map_err(|e| plugin_error(name, e))
+    }
+
+    fn enable_tracing(&mut self) {
+        self.inner.enable_tracing();
+    }
+
+    fn get_trace_log(&self) -> PyResult<String> {
+        self.inner.trace_log()
+            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to serialize trace log: {}", e)))
+    }
+}
+
+/// Collapse a `**kwargs`-style dict into a JSON object string for passing to hook scripts
+fn dict_to_json(dict: Option<&PyDict>) -> PyResult<String> {
+    let Some(dict) = dict else {
+        return Ok("{}".to_string());
+    };
+
+    let mut map = serde_json::Map::new();
+    for (key, value) in dict.iter() {
+        let key: String = key.extract()?;
+        let value: String = value.str()?.extract()?;
+        map.insert(key, serde_json::Value::String(value));
+    }
+
+    serde_json::to_string(&map)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to serialize context: {}", e)))
 }
 
 /// Python wrapper for ThemeManager
@@ -88,17 +158,26 @@ impl ThemeManager {
 
     fn set_theme(&mut self, name: &str) -> PyResult<()> {
         self.inner.set_theme(name)
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to set theme: {}", e)))
+            .map_err(|e| theme_error(name, e))
     }
 
     fn get_theme_color(&self, color_name: &str) -> PyResult<String> {
         self.inner.get_theme_color(color_name)
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to get theme color: {}", e)))
+            .map_err(|_| theme_color_error(color_name))
     }
 
     fn get_theme_variable(&self, var_name: &str) -> PyResult<String> {
         self.inner.get_theme_variable(var_name)
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to get theme variable: {}", e)))
+            .map_err(|_| theme_color_error(var_name))
+    }
+
+    fn get_theme_appearance(&self) -> PyResult<String> {
+        self.inner.get_theme_appearance()
+            .map(|appearance| match appearance {
+                themes::Appearance::Light => "light".to_string(),
+                themes::Appearance::Dark => "dark".to_string(),
+            })
+            .map_err(|e| theme_error("<active>", e))
     }
 
     fn get_themes(&self) -> PyResult<Vec<String>> {
@@ -107,14 +186,14 @@ impl ThemeManager {
 
     fn reload_theme(&mut self) -> PyResult<()> {
         self.inner.reload_theme()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to reload theme: {}", e)))
+            .map_err(|e| theme_error("<active>", e))
     }
 }
 
 /// Config Generator wrapper
 #[pyclass]
 struct ConfigGenerator {
-    
+
 }
 
 #[pymethods]
@@ -124,22 +203,39 @@ impl ConfigGenerator {
         ConfigGenerator {}
     }
 
-    fn generate_config(&self, theme: &str, plugins: Vec<&str>, _additional_config: &str) -> PyResult<String> {
-        // Mock implementation for now
-        Ok(format!("Generated config for theme: {}, plugins: {:?}", theme, plugins))
+    fn generate_config(
+        &self,
+        theme_manager: PyRef<ThemeManager>,
+        mut plugin_manager: PyRefMut<PluginManager>,
+        base_config: &str,
+    ) -> PyResult<String> {
+        let theme = theme_manager.inner.get_active_theme()
+            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("No active theme"))?;
+
+        crate::hyprconf::generate_config(&theme, &mut plugin_manager.inner, base_config)
+            .map_err(|e| PyErr::new::<ConfigConflictError, _>((e.to_string(),)))
     }
 
-    fn detect_conflicts(&self, _config1: &str, _config2: &str) -> PyResult<Vec<String>> {
-        // Mock implementation for now
-        Ok(vec!["No conflicts detected".to_string()])
+    fn detect_conflicts(&self, config1: &str, config2: &str) -> PyResult<String> {
+        let conflicts = crate::hyprconf::detect_conflicts(config1, config2);
+        serde_json::to_string(&conflicts)
+            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to serialize conflicts: {}", e)))
     }
 }
 
 /// A Python module implemented in Rust.
 #[pymodule]
-fn hyprsupreme_core(_py: Python, m: &PyModule) -> PyResult<()> {
+fn hyprsupreme_core(py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<PluginManager>()?;
     m.add_class::<ThemeManager>()?;
     m.add_class::<ConfigGenerator>()?;
+
+    m.add("PluginNotFoundError", py.get_type::<PluginNotFoundError>())?;
+    m.add("PluginExecutionError", py.get_type::<PluginExecutionError>())?;
+    m.add("PluginValidationError", py.get_type::<PluginValidationError>())?;
+    m.add("ThemeNotFoundError", py.get_type::<ThemeNotFoundError>())?;
+    m.add("ThemeColorError", py.get_type::<ThemeColorError>())?;
+    m.add("ConfigConflictError", py.get_type::<ConfigConflictError>())?;
+
     Ok(())
 }
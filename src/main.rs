@@ -1,14 +1,19 @@
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
 use color_eyre::{eyre::WrapErr, Result};
 use std::path::PathBuf;
 
 mod config;
 mod themes;
 mod plugins;
+mod validate;
+mod hyprconf;
+mod xdg;
+mod hooks;
 
 use config::Config;
 use themes::{ThemeManager, ThemeFormat};
-use plugins::{PluginManager, PluginState};
+use plugins::{PluginManager, PluginState, WasmModulePlugin};
 
 /// HyprSupreme-Builder: A tool for managing Hyprland configurations
 #[derive(Parser)]
@@ -30,10 +35,11 @@ struct Cli {
 enum Commands {
     /// Initialize a new Hyprland configuration
     Init {
-        /// Directory to initialize the configuration in
-        #[clap(short, long, value_parser, default_value = ".")]
-        dir: PathBuf,
-        
+        /// Directory to initialize the configuration in (defaults to the
+        /// resolved XDG config directory, e.g. `~/.config/hyprsupreme`)
+        #[clap(short, long, value_parser)]
+        dir: Option<PathBuf>,
+
         /// Template to use for initialization
         #[clap(short, long, default_value = "default")]
         template: String,
@@ -44,12 +50,21 @@ enum Commands {
         /// Configuration file to build
         #[clap(short, long, value_parser)]
         config: Option<PathBuf>,
-        
+
         /// Output directory for built configuration
         #[clap(short, long, value_parser)]
         output: Option<PathBuf>,
+
+        /// Only validate the configuration, without writing any output
+        #[clap(long)]
+        test_config: bool,
+
+        /// Keep running remaining pre-build/post-build hooks after one fails, instead
+        /// of aborting the build at the first failure
+        #[clap(long)]
+        keep_going: bool,
     },
-    
+
     /// Update existing Hyprland configurations
     Update {
         /// Configuration file to update
@@ -71,6 +86,48 @@ enum Commands {
         #[clap(subcommand)]
         command: PluginCommands,
     },
+
+    /// Validate configs and themes against their JSON Schemas
+    Validate {
+        /// Configuration file to validate
+        #[clap(short, long, value_parser)]
+        config: Option<PathBuf>,
+
+        /// Theme directory to validate (every `.toml`/`.json` file in it)
+        #[clap(short, long, value_parser)]
+        themes: Option<PathBuf>,
+
+        /// Treat unrecognized keys as errors instead of tolerating them
+        #[clap(long)]
+        strict: bool,
+    },
+
+    /// Configuration introspection commands
+    Config {
+        #[clap(subcommand)]
+        command: ConfigCommands,
+    },
+
+    /// Generate shell completion scripts
+    Completions {
+        /// Shell to generate completions for
+        shell: Shell,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Print the fully resolved configuration (after variable substitution and
+    /// profile selection) as TOML
+    Dump {
+        /// Configuration file to dump
+        #[clap(short, long, value_parser)]
+        config: Option<PathBuf>,
+
+        /// Profile to resolve (defaults to the config's `default_profile`)
+        #[clap(short, long)]
+        profile: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -98,6 +155,21 @@ enum ThemeCommands {
     Apply {
         /// Name of the theme
         name: String,
+
+        /// Keep running remaining theme-apply hooks after one fails, instead of
+        /// aborting at the first failure
+        #[clap(long)]
+        keep_going: bool,
+    },
+
+    /// Print the resolved theme (or the built-in default) as TOML
+    Dump {
+        /// Name of the theme to dump
+        name: Option<String>,
+
+        /// Dump the built-in default theme instead of a named theme
+        #[clap(long)]
+        default: bool,
     },
 }
 
@@ -135,6 +207,19 @@ enum PluginCommands {
         /// Name of the plugin
         name: String,
     },
+
+    /// Manually invoke one of a plugin's declared commands
+    Run {
+        /// Name of the plugin
+        name: String,
+
+        /// Name of the command, as declared in the plugin's manifest
+        command: String,
+
+        /// Arguments passed through to the command
+        #[clap(trailing_var_arg = true)]
+        args: Vec<String>,
+    },
 }
 
 /// Setup function for initializing logging and error handling
@@ -153,7 +238,20 @@ fn setup() -> Result<()> {
     Ok(())
 }
 
-fn init_command(dir: PathBuf, template: String) -> Result<()> {
+/// Default config file to use when no explicit `--config` is given: the
+/// XDG-resolved config file if it exists, else `./hyprsupreme.toml` so running
+/// straight out of a checkout still works.
+fn default_config_path() -> PathBuf {
+    let xdg_path = xdg::Dirs::resolve().config_file();
+    if xdg_path.exists() {
+        xdg_path
+    } else {
+        PathBuf::from("hyprsupreme.toml")
+    }
+}
+
+fn init_command(dir: Option<PathBuf>, template: String) -> Result<()> {
+    let dir = dir.unwrap_or_else(|| xdg::Dirs::resolve().config_dir);
     println!("Initializing new configuration in {:?} using template '{}'", dir, template);
     
     // Create directory if it doesn't exist
@@ -178,43 +276,88 @@ fn init_command(dir: PathBuf, template: String) -> Result<()> {
     Ok(())
 }
 
-fn build_command(config: Option<PathBuf>, output: Option<PathBuf>) -> Result<()> {
-    let config_path = config.unwrap_or_else(|| PathBuf::from("hyprsupreme.toml"));
+fn build_command(config: Option<PathBuf>, output: Option<PathBuf>, test_config: bool, keep_going: bool) -> Result<()> {
+    let config_path = config.unwrap_or_else(default_config_path);
+
+    if test_config {
+        let report = validate::validate_config_file(&config_path, false)
+            .wrap_err_with(|| format!("Failed to validate configuration at: {:?}", config_path))?;
+
+        if report.is_valid() {
+            println!("Configuration is valid: {:?}", config_path);
+            return Ok(());
+        }
+
+        for error in &report.errors {
+            println!("  {}", error);
+        }
+        return Err(color_eyre::eyre::eyre!(report.summary()));
+    }
+
     let output_dir = output.unwrap_or_else(|| PathBuf::from("build"));
-    
+
     println!("Building configuration from {:?} to {:?}", config_path, output_dir);
-    
+
     // Load configuration
     let config = Config::from_file(&config_path)
         .wrap_err_with(|| format!("Failed to load configuration from: {:?}", config_path))?;
-    
+
     // Create output directory if it doesn't exist
     if !output_dir.exists() {
         std::fs::create_dir_all(&output_dir)
             .wrap_err_with(|| format!("Failed to create output directory: {:?}", output_dir))?;
     }
-    
+
     // Get active profile
     let profile = config.get_active_profile(None)?;
-    
+
     println!("Using profile: {}", config.default_profile);
+
+    let mut plugin_manager = PluginManager::default();
+    plugin_manager.initialize().wrap_err("Failed to initialize plugin manager")?;
+    plugin_manager.scan_all_wasm_plugins().wrap_err("Failed to scan WASM plugins")?;
+
+    hooks::run_lifecycle(&plugin_manager, hooks::Lifecycle::PreBuild, &[], keep_going)
+        .wrap_err("Pre-build hooks failed")?;
+
     println!("Resolving variables and generating configuration files...");
-    
+
     // Example of variable resolution
-    if let Some(_) = profile.variables.get("terminal") {
-        let resolved = config.resolve_variables(&format!("Terminal: ${{terminal}}"), None);
-        println!("Example variable resolution: {}", resolved);
+    if profile.variables.contains_key("terminal") {
+        match config.resolve_variables("Terminal: ${terminal}", None, None) {
+            Ok(resolved) => println!("Example variable resolution: {}", resolved),
+            Err(err) => println!("Failed to resolve variables: {}", err),
+        }
     }
-    
-    // TODO: Generate Hyprland configuration files
-    
+
+    let theme_manager = ThemeManager::default();
+    let theme = theme_manager.get_active_theme().unwrap_or_else(themes::Theme::default_theme);
+
+    let base_config = match &config.hyprland.config_path {
+        Some(path) if path.exists() => std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("Failed to read base Hyprland config: {:?}", path))?,
+        _ => String::new(),
+    };
+
+    let generated = hyprconf::generate_config(&theme, &mut plugin_manager, &base_config)
+        .wrap_err("Failed to generate Hyprland configuration")?;
+
+    let generated_path = output_dir.join("hyprland.conf");
+    std::fs::write(&generated_path, &generated)
+        .wrap_err_with(|| format!("Failed to write generated configuration to: {:?}", generated_path))?;
+
+    println!("Wrote generated configuration to: {:?}", generated_path);
+
+    hooks::run_lifecycle(&plugin_manager, hooks::Lifecycle::PostBuild, &[], keep_going)
+        .wrap_err("Post-build hooks failed")?;
+
     println!("Build completed successfully!");
-    
+
     Ok(())
 }
 
 fn update_command(config: Option<PathBuf>, component: Option<String>) -> Result<()> {
-    let config_path = config.unwrap_or_else(|| PathBuf::from("hyprsupreme.toml"));
+    let config_path = config.unwrap_or_else(default_config_path);
     
     match &component {
         Some(comp) => println!("Updating component '{}' in {:?}", comp, config_path),
@@ -248,7 +391,105 @@ fn update_command(config: Option<PathBuf>, component: Option<String>) -> Result<
     } else {
         println!("Full configuration update not implemented yet");
     }
-    
+
+    Ok(())
+}
+
+fn validate_command(config: Option<PathBuf>, themes: Option<PathBuf>, strict: bool) -> Result<()> {
+    let config_path = config.unwrap_or_else(default_config_path);
+    let themes_dir = themes.or_else(|| {
+        ThemeManager::default().loader().theme_dirs().iter().find(|dir| dir.exists()).cloned()
+    });
+
+    let mut reports = Vec::new();
+
+    reports.push(
+        validate::validate_config_file(&config_path, strict)
+            .wrap_err_with(|| format!("Failed to validate configuration at: {:?}", config_path))?,
+    );
+
+    if let Some(themes_dir) = &themes_dir {
+        for entry in walkdir::WalkDir::new(themes_dir).into_iter().filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let is_theme_file = path.extension()
+                .and_then(|ext| ext.to_str())
+                .map(|ext| ext == "toml" || ext == "json")
+                .unwrap_or(false);
+
+            if !path.is_file() || !is_theme_file {
+                continue;
+            }
+
+            reports.push(
+                validate::validate_theme_file(path, strict)
+                    .wrap_err_with(|| format!("Failed to validate theme file: {:?}", path))?,
+            );
+        }
+    }
+
+    let mut total_errors = 0;
+    let mut summaries = Vec::new();
+
+    for report in &reports {
+        if !report.is_valid() {
+            for error in &report.errors {
+                println!("{}: {}", report.file, error);
+            }
+            total_errors += report.errors.len();
+            summaries.push(report.summary());
+        }
+    }
+
+    if total_errors == 0 {
+        let file_count = reports.len();
+        println!("All {} file{} valid", file_count, if file_count == 1 { "" } else { "s" });
+        Ok(())
+    } else {
+        println!("{}", summaries.join(", "));
+        Err(color_eyre::eyre::eyre!("{} validation error{} found", total_errors, if total_errors == 1 { "" } else { "s" }))
+    }
+}
+
+fn config_dump_command(config: Option<PathBuf>, profile: Option<String>) -> Result<()> {
+    let config_path = config.unwrap_or_else(default_config_path);
+
+    let config = Config::from_file(&config_path)
+        .wrap_err_with(|| format!("Failed to load configuration from: {:?}", config_path))?;
+
+    let theme_manager = ThemeManager::default();
+    let resolved = config.resolved(profile.as_deref(), Some(&theme_manager))
+        .wrap_err("Failed to resolve configuration")?;
+
+    let toml_string = toml::to_string_pretty(&resolved)
+        .wrap_err("Failed to serialize resolved configuration")?;
+
+    print!("{}", toml_string);
+
+    Ok(())
+}
+
+fn theme_dump_command(name: Option<String>, default: bool) -> Result<()> {
+    let theme = if default {
+        themes::Theme::default_theme()
+    } else {
+        let name = name.ok_or_else(|| color_eyre::eyre::eyre!("Provide a theme name, or pass --default"))?;
+        let theme_manager = ThemeManager::default();
+        theme_manager.loader().load_theme(&name)
+            .wrap_err_with(|| format!("Failed to load theme: {}", name))?
+    };
+
+    let toml_string = toml::to_string_pretty(&theme)
+        .wrap_err("Failed to serialize theme")?;
+
+    print!("{}", toml_string);
+
+    Ok(())
+}
+
+fn completions_command(shell: Shell) -> Result<()> {
+    let mut cmd = Cli::command();
+    let name = cmd.get_name().to_string();
+    clap_complete::generate(shell, &mut cmd, name, &mut std::io::stdout());
     Ok(())
 }
 
@@ -269,8 +510,8 @@ fn main() -> Result<()> {
         Commands::Init { dir, template } => {
             init_command(dir, template)?;
         },
-        Commands::Build { config, output } => {
-            build_command(config, output)?;
+        Commands::Build { config, output, test_config, keep_going } => {
+            build_command(config, output, test_config, keep_going)?;
         },
         Commands::Update { config, component } => {
             update_command(config, component)?;
@@ -288,8 +529,8 @@ fn main() -> Result<()> {
                 },
                 ThemeCommands::Show { name } => {
                     let theme_manager = ThemeManager::default();
-                    match theme_manager.loader().load_theme(&name) {
-                        Ok(theme) => {
+                    match theme_manager.loader().load_theme_with_provenance(&name) {
+                        Ok((theme, provenance)) => {
                             println!("Theme: {}", theme.name);
                             if let Some(author) = &theme.author {
                                 println!("Author: {}", author);
@@ -298,15 +539,23 @@ fn main() -> Result<()> {
                                 println!("Description: {}", description);
                             }
                             println!("Version: {}", theme.version);
-                            
+
                             println!("\nColors:");
                             for (name, value) in &theme.colors {
-                                println!("  {}: {}", name, value);
+                                let key = format!("colors.{}", name);
+                                match provenance.get(&key) {
+                                    Some(source) if source != &theme.name => println!("  {}: {} (from {})", name, value, source),
+                                    _ => println!("  {}: {}", name, value),
+                                }
                             }
-                            
+
                             println!("\nVariables:");
                             for (name, value) in &theme.variables {
-                                println!("  {}: {}", name, value);
+                                let key = format!("variables.{}", name);
+                                match provenance.get(&key) {
+                                    Some(source) if source != &theme.name => println!("  {}: {} (from {})", name, value, source),
+                                    _ => println!("  {}: {}", name, value),
+                                }
                             }
                         },
                         Err(err) => {
@@ -343,28 +592,61 @@ fn main() -> Result<()> {
                         }
                     }
                 },
-                ThemeCommands::Apply { name } => {
+                ThemeCommands::Apply { name, keep_going } => {
                     let mut theme_manager = ThemeManager::default();
                     match theme_manager.set_theme(&name) {
                         Ok(_) => {
                             println!("Applied theme: {}", name);
-                            // TODO: Generate and apply Hyprland configuration
+
+                            let mut plugin_manager = PluginManager::default();
+                            plugin_manager.initialize().wrap_err("Failed to initialize plugin manager")?;
+                            plugin_manager.scan_all_wasm_plugins().wrap_err("Failed to scan WASM plugins")?;
+                            hooks::run_lifecycle(&plugin_manager, hooks::Lifecycle::ThemeApply, &[&name], keep_going)
+                                .wrap_err("Theme-apply hooks failed")?;
+
+                            let theme = theme_manager.get_active_theme()
+                                .ok_or_else(|| color_eyre::eyre::eyre!("Theme '{}' was applied but is not active", name))?;
+
+                            let config_path = default_config_path();
+                            let base_config = Config::from_file(&config_path).ok()
+                                .and_then(|config| config.hyprland.config_path)
+                                .filter(|path| path.exists())
+                                .map(std::fs::read_to_string)
+                                .transpose()
+                                .wrap_err("Failed to read base Hyprland config")?
+                                .unwrap_or_default();
+
+                            let generated = hyprconf::generate_config(&theme, &mut plugin_manager, &base_config)
+                                .wrap_err("Failed to generate Hyprland configuration")?;
+
+                            let generated_path = xdg::Dirs::resolve().config_dir.join("hyprland.conf");
+                            std::fs::write(&generated_path, &generated)
+                                .wrap_err_with(|| format!("Failed to write generated configuration to: {:?}", generated_path))?;
+
+                            println!("Wrote generated configuration to: {:?}", generated_path);
                         },
                         Err(err) => {
                             println!("Error applying theme '{}': {}", name, err);
                         }
                     }
                 },
+                ThemeCommands::Dump { name, default } => {
+                    theme_dump_command(name, default)?;
+                },
             }
         },
+        Commands::Validate { config, themes, strict } => {
+            validate_command(config, themes, strict)?;
+        },
         Commands::Plugin { command } => {
             let mut plugin_manager = PluginManager::default();
             plugin_manager.initialize()?;
-            
+            plugin_manager.scan_all_wasm_plugins()?;
+
             match command {
                 PluginCommands::List => {
                     let plugin_names = plugin_manager.get_plugins();
-                    
+
                     println!("Available plugins:");
                     for plugin_name in plugin_names {
                         if let Some(plugin) = plugin_manager.get_plugin(&plugin_name) {
@@ -374,13 +656,27 @@ fn main() -> Result<()> {
                                 PluginState::NotInstalled => "not installed",
                                 PluginState::Error(_) => "error",
                             };
-                            
+
                             println!("  - {} (v{}) [{}]", plugin.manifest.name, plugin.manifest.version, status);
                             if let Some(desc) = &plugin.manifest.description {
                                 println!("    {}", desc);
                             }
                         }
                     }
+
+                    if !plugin_manager.wasm_plugins().is_empty() {
+                        println!("\nWASM modules:");
+                        for module in plugin_manager.wasm_plugins() {
+                            match module {
+                                WasmModulePlugin::Initialized { info, .. } => {
+                                    println!("  - {} (v{}) [{}]", info.name, info.version, module.path().display());
+                                }
+                                WasmModulePlugin::Failed { path, error } => {
+                                    println!("  - {} [failed: {}]", path.display(), error);
+                                }
+                            }
+                        }
+                    }
                 },
                 PluginCommands::Show { name } => {
                     match plugin_manager.get_plugin(&name) {
@@ -422,7 +718,19 @@ fn main() -> Result<()> {
                             }
                         },
                         None => {
-                            println!("Plugin '{}' not found", name);
+                            let wasm_module = plugin_manager.wasm_plugins().iter()
+                                .find(|module| matches!(module, WasmModulePlugin::Initialized { info, .. } if info.name == name));
+
+                            match wasm_module {
+                                Some(WasmModulePlugin::Initialized { path, info, verified, .. }) => {
+                                    println!("Plugin: {} (WASM module: {})", info.name, path.display());
+                                    println!("Version: {}", info.version);
+                                    if let Err(reason) = verified {
+                                        println!("Verified: no ({})", reason);
+                                    }
+                                }
+                                _ => println!("Plugin '{}' not found", name),
+                            }
                         }
                     }
                 },
@@ -466,9 +774,32 @@ fn main() -> Result<()> {
                         }
                     }
                 },
+                PluginCommands::Run { name, command, args } => {
+                    let plugin = plugin_manager.get_plugin(&name)
+                        .ok_or_else(|| color_eyre::eyre::eyre!("Plugin not found: {}", name))?;
+                    let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+
+                    let invocation = hooks::run_command(plugin, &command, &arg_refs)?;
+                    if !invocation.success {
+                        return Err(color_eyre::eyre::eyre!(
+                            "Command '{}' failed for plugin '{}'; see log: {:?}",
+                            command, name, invocation.log_path,
+                        ));
+                    }
+                },
             }
         },
+        Commands::Config { command } => {
+            match command {
+                ConfigCommands::Dump { config, profile } => {
+                    config_dump_command(config, profile)?;
+                },
+            }
+        },
+        Commands::Completions { shell } => {
+            completions_command(shell)?;
+        },
     }
-    
+
     Ok(())
 }
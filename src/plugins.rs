@@ -1,11 +1,345 @@
 use color_eyre::{eyre::Context, Result};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use libloading::{Library, Symbol};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::fs;
+use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::Mutex;
 use semver::{Version, VersionReq};
 
+/// Symbol a native plugin library must export: a C-ABI constructor that returns an
+/// owning pointer to a boxed [`NativePlugin`] trait object.
+const NATIVE_PLUGIN_CONSTRUCTOR_SYMBOL: &[u8] = b"_hyprsupreme_plugin_create\0";
+
+/// This host's JSON-RPC protocol version, checked against each `PluginRuntime::Daemon`
+/// plugin's declared `protocol_version` requirement before it is spawned.
+const HOST_PROTOCOL_VERSION: &str = "1.0.0";
+
+/// Check whether `host_version` (a concrete semver) satisfies `requirement` (a semver
+/// requirement string), reusing the same version-matching `PluginManifest::satisfies_requirement`
+/// uses for plugin dependencies.
+pub fn versions_compatible(host_version: &str, requirement: &str) -> Result<bool> {
+    let req = VersionReq::parse(requirement)
+        .with_context(|| format!("Invalid protocol version requirement: {}", requirement))?;
+    let version = Version::parse(host_version)
+        .with_context(|| format!("Invalid host protocol version: {}", host_version))?;
+
+    Ok(req.matches(&version))
+}
+
+/// Compute a canonical SHA-256 digest over a plugin's manifest plus the script/WASM/
+/// library/entry files its runtime declares, so a signature covers the code that
+/// actually runs and not just the metadata describing it. Files are hashed in a fixed
+/// (sorted, deduplicated) order so the digest doesn't depend on directory iteration
+/// order or a file being referenced more than once.
+fn compute_plugin_digest(manifest: &PluginManifest, dir: &Path) -> Result<[u8; 32]> {
+    let mut hasher = Sha256::new();
+
+    let canonical = serde_json::to_vec(manifest)
+        .with_context(|| "Failed to serialize plugin manifest for signature digest")?;
+    hasher.update(&canonical);
+
+    let runtime_file = match &manifest.runtime {
+        PluginRuntime::Script => None,
+        PluginRuntime::Wasm { module, .. } => Some(module.clone()),
+        PluginRuntime::Native { library } => Some(library.clone()),
+        PluginRuntime::Daemon { entry, .. } => Some(entry.clone()),
+    };
+
+    // A WASM plugin's hooks/commands name exported functions of its module, not files
+    let script_files: Vec<String> = match &manifest.runtime {
+        PluginRuntime::Wasm { .. } => Vec::new(),
+        _ => manifest.hooks.iter().map(|hook| hook.script.clone())
+            .chain(manifest.commands.iter().map(|command| command.script.clone()))
+            .collect(),
+    };
+
+    let mut relative_paths: Vec<String> = script_files.into_iter()
+        .chain(runtime_file)
+        .collect();
+    relative_paths.sort();
+    relative_paths.dedup();
+
+    for relative in relative_paths {
+        let path = dir.join(&relative);
+        let bytes = fs::read(&path)
+            .with_context(|| format!("Failed to read '{}' for signature digest", path.display()))?;
+        hasher.update(&bytes);
+    }
+
+    Ok(hasher.finalize().into())
+}
+
+/// Lifecycle callbacks implemented by a native (dynamic-library) plugin. The library
+/// exports a `_hyprsupreme_plugin_create` constructor returning `*mut dyn NativePlugin`;
+/// `PluginManager` owns the resulting box for as long as the plugin is loaded and drives
+/// these callbacks around the library's own lifetime.
+pub trait NativePlugin {
+    /// Called once, immediately after the library is loaded and before it is enabled
+    fn on_load(&mut self) {}
+
+    /// Called each time the plugin is enabled
+    fn on_enable(&mut self) {}
+
+    /// Called each time the plugin is disabled
+    fn on_disable(&mut self) {}
+
+    /// Called just before the library is unloaded. Plugins must release any resources
+    /// tied to the library's code here: nothing in the library can run once this returns.
+    fn on_unload(&mut self) {}
+}
+
+/// A native plugin's instance together with the `Library` handle that defines it, kept
+/// alive for as long as the plugin is loaded so the instance's vtable stays mapped.
+struct LoadedNativePlugin {
+    instance: Box<dyn NativePlugin>,
+    _library: Library,
+}
+
+impl std::fmt::Debug for LoadedNativePlugin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoadedNativePlugin").finish_non_exhaustive()
+    }
+}
+
+/// A `.wasm` file discovered directly in a plugin directory, with no side-car `plugin.toml`/
+/// `plugin.json` manifest: it describes itself by exporting a `plugin_info` function that
+/// returns its `PluginManifest` (name/version/hooks/commands) as JSON. This is a second
+/// plugin backend that coexists with the directory+manifest plugins tracked in
+/// `PluginManager::plugins` rather than replacing them.
+pub enum WasmModulePlugin {
+    /// The module instantiated and its `plugin_info` export returned a manifest
+    Initialized {
+        path: PathBuf,
+        info: PluginManifest,
+        instance: Mutex<extism::Plugin>,
+        verified: std::result::Result<(), String>,
+    },
+
+    /// The module failed to instantiate, or its `plugin_info` export failed or didn't
+    /// return something that parses as a `PluginManifest`
+    Failed { path: PathBuf, error: String },
+}
+
+impl std::fmt::Debug for WasmModulePlugin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WasmModulePlugin::Initialized { path, info, verified, .. } => f
+                .debug_struct("WasmModulePlugin::Initialized")
+                .field("path", path)
+                .field("info", info)
+                .field("verified", verified)
+                .finish_non_exhaustive(),
+            WasmModulePlugin::Failed { path, error } => f
+                .debug_struct("WasmModulePlugin::Failed")
+                .field("path", path)
+                .field("error", error)
+                .finish(),
+        }
+    }
+}
+
+impl WasmModulePlugin {
+    /// Path to the `.wasm` file, whether or not it loaded successfully
+    pub fn path(&self) -> &Path {
+        match self {
+            WasmModulePlugin::Initialized { path, .. } => path,
+            WasmModulePlugin::Failed { path, .. } => path,
+        }
+    }
+}
+
+/// A long-lived `PluginRuntime::Daemon` child process, spawned once at enable time and
+/// driven over newline-delimited JSON-RPC (`{"method", "params", "id"}` requests,
+/// matched against responses by `id`) instead of being re-invoked per call.
+struct ChildPluginProcess {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: u64,
+}
+
+impl std::fmt::Debug for ChildPluginProcess {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChildPluginProcess").field("pid", &self.child.id()).finish()
+    }
+}
+
+impl ChildPluginProcess {
+    /// Spawn `entry_path` with piped stdin/stdout, running in `cwd`.
+    fn spawn(entry_path: &Path, cwd: &Path) -> Result<Self> {
+        let mut child = Command::new(entry_path)
+            .current_dir(cwd)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .with_context(|| format!("Failed to spawn plugin daemon: {}", entry_path.display()))?;
+
+        let stdin = child.stdin.take()
+            .ok_or_else(|| color_eyre::eyre::eyre!("Failed to open stdin for plugin daemon: {}", entry_path.display()))?;
+        let stdout = child.stdout.take()
+            .ok_or_else(|| color_eyre::eyre::eyre!("Failed to open stdout for plugin daemon: {}", entry_path.display()))?;
+
+        Ok(ChildPluginProcess {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+            next_id: 0,
+        })
+    }
+
+    /// Whether the child is still running. Reaps it (without blocking) if it has exited.
+    fn is_alive(&mut self) -> bool {
+        matches!(self.child.try_wait(), Ok(None))
+    }
+
+    /// Frame `method`/`params` as a JSON-RPC request, write it to the child's stdin,
+    /// and block reading newline-delimited JSON from stdout until the response with
+    /// the matching `id` arrives. Returns its `result` field (as a string, or its JSON
+    /// serialization if not already a string), or an error built from its `error` field.
+    fn call(&mut self, method: &str, params: &[&str]) -> Result<String> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let request = serde_json::json!({ "method": method, "params": params, "id": id });
+        writeln!(self.stdin, "{}", request)
+            .with_context(|| format!("Failed to write '{}' request to plugin daemon", method))?;
+        self.stdin.flush()
+            .with_context(|| "Failed to flush plugin daemon stdin")?;
+
+        loop {
+            let mut line = String::new();
+            let bytes_read = self.stdout.read_line(&mut line)
+                .with_context(|| format!("Failed to read response to '{}' from plugin daemon", method))?;
+
+            if bytes_read == 0 {
+                return Err(color_eyre::eyre::eyre!(
+                    "Plugin daemon closed stdout while awaiting a response to '{}'", method
+                ));
+            }
+
+            let response: serde_json::Value = match serde_json::from_str(line.trim()) {
+                Ok(value) => value,
+                Err(_) => continue, // ignore stray non-JSON-RPC output on stdout
+            };
+
+            if response.get("id").and_then(|v| v.as_u64()) != Some(id) {
+                continue;
+            }
+
+            if let Some(error) = response.get("error") {
+                return Err(color_eyre::eyre::eyre!("Plugin daemon returned an error for '{}': {}", method, error));
+            }
+
+            return Ok(match response.get("result") {
+                Some(serde_json::Value::String(s)) => s.clone(),
+                Some(value) => value.to_string(),
+                None => String::new(),
+            });
+        }
+    }
+
+    /// Ask the daemon to shut down cleanly via a `shutdown` JSON-RPC call, giving it
+    /// `timeout` to exit on its own; if it hasn't, escalate to `SIGTERM` and wait
+    /// `timeout` again; if it's still alive after that, `SIGKILL` it.
+    fn shutdown(mut self, timeout: std::time::Duration) -> Result<()> {
+        // Send the request without waiting for a reply: `call` would block indefinitely
+        // on a daemon that never answers, before the timeout below could apply.
+        let request = serde_json::json!({ "method": "shutdown", "params": [], "id": self.next_id });
+        self.next_id += 1;
+        let _ = writeln!(self.stdin, "{}", request).and_then(|_| self.stdin.flush());
+
+        if self.wait_for_exit(timeout) {
+            return Ok(());
+        }
+
+        // Safety: `id()` is this child's own process id, valid for the lifetime of `self`.
+        unsafe {
+            libc::kill(self.child.id() as libc::pid_t, libc::SIGTERM);
+        }
+
+        if self.wait_for_exit(timeout) {
+            return Ok(());
+        }
+
+        self.child.kill().with_context(|| "Failed to SIGKILL plugin daemon")?;
+        self.child.wait().with_context(|| "Failed to reap plugin daemon after SIGKILL")?;
+
+        Ok(())
+    }
+
+    fn wait_for_exit(&mut self, timeout: std::time::Duration) -> bool {
+        let deadline = std::time::Instant::now() + timeout;
+        while std::time::Instant::now() < deadline {
+            if matches!(self.child.try_wait(), Ok(Some(_))) {
+                return true;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(25));
+        }
+        false
+    }
+}
+
+/// Typed errors from plugin lifecycle operations, matchable by callers instead of
+/// string-sniffing an `eyre!` report.
+#[derive(Debug, thiserror::Error)]
+pub enum PluginError {
+    /// No plugin is registered under this name
+    #[error("Plugin not found: {0}")]
+    NotFound(String),
+
+    /// `plugin` declares a dependency on `dependency`, which is not registered
+    #[error("Missing dependency: {plugin} requires {dependency}")]
+    MissingDependency { plugin: String, dependency: String },
+
+    /// `plugin` requires `dependency` to satisfy `requirement`, but the registered
+    /// version does not
+    #[error("Dependency version mismatch: {plugin} requires {dependency} {requirement}")]
+    VersionMismatch {
+        plugin: String,
+        dependency: String,
+        requirement: String,
+    },
+
+    /// The dependency graph contains a cycle; the path is the sequence of plugin names
+    /// that closes it, e.g. `["a", "b", "a"]`
+    #[error("Dependency cycle detected: {}", .0.join(" -> "))]
+    DependencyCycle(Vec<String>),
+
+    /// `plugin` cannot be uninstalled while `dependent` still depends on it
+    #[error("Plugin '{plugin}' is still required by '{dependent}'")]
+    InUseBy { plugin: String, dependent: String },
+
+    /// `plugin`'s declared `requirement` on the daemon JSON-RPC protocol doesn't match
+    /// the host's `host_version`
+    #[error("Plugin '{plugin}' protocol version incompatible: host is {host_version}, plugin requires {requirement}")]
+    ProtocolIncompatible {
+        plugin: String,
+        host_version: String,
+        requirement: String,
+    },
+
+    /// Two plugins both declare the same `(kind, name)` capability and neither (or both)
+    /// marked it `default`, so the catalogue can't pick one to serve it
+    #[error("Conflicting providers for {kind} '{name}': '{first}' and '{second}' (mark one `default: true` to resolve)")]
+    CapabilityConflict {
+        kind: String,
+        name: String,
+        first: String,
+        second: String,
+    },
+
+    /// `plugin`'s signature didn't verify against any trusted key (or it has none),
+    /// and the active `SignaturePolicy` is `Strict`
+    #[error("Plugin '{plugin}' is not trusted ({reason}) and the signature policy is strict")]
+    Untrusted { plugin: String, reason: String },
+}
+
 /// Plugin manifest structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PluginManifest {
@@ -51,6 +385,158 @@ pub struct PluginManifest {
     /// Configuration schema
     #[serde(default)]
     pub config_schema: Option<serde_json::Value>,
+
+    /// Symbol (module path, function name, etc.) identifying the code that implements
+    /// the plugin interface, advertised by third-party plugins discovered on disk
+    #[serde(default)]
+    pub entry_point: Option<String>,
+
+    /// Execution backend this plugin's hooks/commands run under
+    #[serde(default)]
+    pub runtime: PluginRuntime,
+
+    /// Maintainer scripts run around install/uninstall file operations
+    #[serde(default)]
+    pub lifecycle: PluginLifecycleScripts,
+
+    /// Capabilities this plugin claims, aggregated by `PluginManager` into a catalogue
+    /// so subsystems can look up the one plugin that handles a given theme, filetype,
+    /// etc. instead of broadcasting to every enabled plugin
+    #[serde(default)]
+    pub provides: Vec<PluginCapability>,
+}
+
+/// Optional maintainer scripts run around install/uninstall file operations, mirroring
+/// the dpkg-style `preinst`/`postinst`/`prerm`/`postrm` hooks. Each is a path (relative
+/// to the plugin directory) to an executable script, invoked with a single argument
+/// naming the operation (`install` or `upgrade`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PluginLifecycleScripts {
+    /// Run before files are copied into place during install/upgrade
+    #[serde(default)]
+    pub preinst: Option<String>,
+
+    /// Run after files have been copied into place during install/upgrade
+    #[serde(default)]
+    pub postinst: Option<String>,
+
+    /// Run before the plugin directory is removed during uninstall
+    #[serde(default)]
+    pub prerm: Option<String>,
+
+    /// Run after the plugin directory has been removed during uninstall
+    #[serde(default)]
+    pub postrm: Option<String>,
+}
+
+/// Whether `install_plugin` is placing a plugin for the first time or replacing an
+/// existing installation, passed as the lifecycle scripts' single argument
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallOperation {
+    /// No previous installation existed at the target directory
+    Install,
+
+    /// An existing installation at the target directory is being replaced
+    Upgrade,
+}
+
+impl InstallOperation {
+    fn as_arg(&self) -> &'static str {
+        match self {
+            InstallOperation::Install => "install",
+            InstallOperation::Upgrade => "upgrade",
+        }
+    }
+}
+
+/// What `enable_plugin` does when a plugin's `verified` outcome (set by `install_plugin`)
+/// is an `Err`: it has no signature file, or its signature didn't verify against any of
+/// the manager's trusted keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignaturePolicy {
+    /// Refuse to enable an unverified plugin
+    Strict,
+
+    /// Log a warning via `tracing::warn!` and enable it anyway
+    Warn,
+}
+
+impl Default for SignaturePolicy {
+    fn default() -> Self {
+        SignaturePolicy::Warn
+    }
+}
+
+/// A single typed capability claim, e.g. `{ kind: "theme", name: "catppuccin" }` or
+/// `{ kind: "filetype", name: "kdl" }`. `kind` is an open-ended category (`"theme"`,
+/// `"filetype"`, `"integration"`, ...) rather than a fixed enum, so new subsystems can
+/// adopt the catalogue without a manifest schema change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginCapability {
+    /// Capability category, e.g. `"theme"` or `"filetype"`
+    pub kind: String,
+
+    /// Capability identifier within `kind` -- a theme name, a filetype extension
+    /// (without the leading dot), an integration name, etc.
+    pub name: String,
+
+    /// If another enabled plugin also claims `(kind, name)`, the one with `default`
+    /// set wins instead of the registration being treated as a conflict
+    #[serde(default)]
+    pub default: bool,
+}
+
+/// Execution backend a plugin's hooks/commands run under
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum PluginRuntime {
+    /// Hooks/commands are on-disk scripts invoked as subprocesses (the default)
+    Script,
+
+    /// Hooks/commands are exported functions in a sandboxed WASM module, run
+    /// through the extism runtime with host-function access limited to an
+    /// explicit allow-list
+    Wasm {
+        /// Path (relative to the plugin directory) to the `.wasm` module
+        module: String,
+
+        /// Filesystem paths the module may access, mapped guest-path -> host-path
+        #[serde(default)]
+        allowed_paths: HashMap<String, String>,
+
+        /// Environment variable names the module may read via its config
+        #[serde(default)]
+        allowed_vars: Vec<String>,
+    },
+
+    /// A native shared library, `dlopen`ed in-process at enable time and kept loaded
+    /// while the plugin is enabled, so the plugin can hold long-lived state. The library
+    /// receives only the `NativePlugin` lifecycle callbacks; hooks/commands still run as
+    /// on-disk scripts, as for `Script`
+    Native {
+        /// Path (relative to the plugin directory) to the shared library
+        /// (`.so`/`.dylib`/`.dll`)
+        library: String,
+    },
+
+    /// Hooks/commands are relayed over newline-delimited JSON-RPC to a long-lived
+    /// child process, spawned once at enable time rather than per invocation, so the
+    /// plugin can hold in-memory state across calls
+    Daemon {
+        /// Path (relative to the plugin directory) to the entry script/executable
+        /// run as the daemon
+        entry: String,
+
+        /// Semver requirement on the host's JSON-RPC protocol version
+        /// (see [`HOST_PROTOCOL_VERSION`]) that this plugin requires
+        protocol_version: String,
+    },
+}
+
+impl Default for PluginRuntime {
+    fn default() -> Self {
+        PluginRuntime::Script
+    }
 }
 
 fn default_version() -> String {
@@ -100,6 +586,10 @@ impl PluginManifest {
             hooks: Vec::new(),
             commands: Vec::new(),
             config_schema: None,
+            entry_point: None,
+            runtime: PluginRuntime::default(),
+            lifecycle: PluginLifecycleScripts::default(),
+            provides: Vec::new(),
         }
     }
     
@@ -185,16 +675,37 @@ pub enum PluginState {
 }
 
 /// Plugin instance
-#[derive(Debug)]
 pub struct Plugin {
     /// Plugin manifest
     pub manifest: PluginManifest,
-    
+
     /// Plugin directory
     pub directory: PathBuf,
-    
+
     /// Plugin state
     pub state: PluginState,
+
+    /// Outcome of detached-signature verification against the manager's trusted keys:
+    /// `Ok(())` once a trusted key's signature has been confirmed over this plugin's
+    /// manifest and code, `Err(reason)` if it's unsigned or the signature didn't
+    /// verify. Set by `install_plugin`; defaults to `Ok(())` for plugins loaded by
+    /// `discover_plugins`/`initialize`, which predate this check.
+    pub verified: std::result::Result<(), String>,
+
+    /// Instantiated WASM module, cached after first use; `None` until then
+    /// (and always `None` for `PluginRuntime::Script` plugins)
+    wasm_plugin: Mutex<Option<extism::Plugin>>,
+}
+
+impl std::fmt::Debug for Plugin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Plugin")
+            .field("manifest", &self.manifest)
+            .field("directory", &self.directory)
+            .field("state", &self.state)
+            .field("verified", &self.verified)
+            .finish()
+    }
 }
 
 impl Plugin {
@@ -204,62 +715,119 @@ impl Plugin {
             manifest,
             directory,
             state: PluginState::Installed,
+            verified: Ok(()),
+            wasm_plugin: Mutex::new(None),
         }
     }
-    
+
     /// Execute a plugin hook
     pub fn execute_hook(&self, hook_name: &str, args: &[&str]) -> Result<String> {
         if let Some(hook) = self.manifest.hooks.iter().find(|h| h.name == hook_name) {
+            if let PluginRuntime::Wasm { .. } = &self.manifest.runtime {
+                return self.call_wasm_export(&hook.script, args);
+            }
+
             let script_path = self.directory.join(&hook.script);
-            
+
             if !script_path.exists() {
                 return Err(color_eyre::eyre::eyre!("Hook script not found: {}", script_path.display()));
             }
-            
+
             let output = Command::new(&script_path)
                 .args(args)
                 .current_dir(&self.directory)
                 .output()
                 .with_context(|| format!("Failed to execute hook script: {}", script_path.display()))?;
-            
+
             if !output.status.success() {
                 let error = String::from_utf8_lossy(&output.stderr).to_string();
                 return Err(color_eyre::eyre::eyre!("Hook script failed: {}", error));
             }
-            
+
             let stdout = String::from_utf8_lossy(&output.stdout).to_string();
             Ok(stdout)
         } else {
             Err(color_eyre::eyre::eyre!("Hook not found: {}", hook_name))
         }
     }
-    
+
     /// Execute a plugin command
     pub fn execute_command(&self, command_name: &str, args: &[&str]) -> Result<String> {
         if let Some(command) = self.manifest.commands.iter().find(|c| c.name == command_name) {
+            if let PluginRuntime::Wasm { .. } = &self.manifest.runtime {
+                return self.call_wasm_export(&command.script, args);
+            }
+
             let script_path = self.directory.join(&command.script);
-            
+
             if !script_path.exists() {
                 return Err(color_eyre::eyre::eyre!("Command script not found: {}", script_path.display()));
             }
-            
+
             let output = Command::new(&script_path)
                 .args(args)
                 .current_dir(&self.directory)
                 .output()
                 .with_context(|| format!("Failed to execute command script: {}", script_path.display()))?;
-            
+
             if !output.status.success() {
                 let error = String::from_utf8_lossy(&output.stderr).to_string();
                 return Err(color_eyre::eyre::eyre!("Command script failed: {}", error));
             }
-            
+
             let stdout = String::from_utf8_lossy(&output.stdout).to_string();
             Ok(stdout)
         } else {
             Err(color_eyre::eyre::eyre!("Command not found: {}", command_name))
         }
     }
+
+    /// Call an exported function in this plugin's sandboxed WASM module, instantiating
+    /// (and caching) the module on first use. `args` are joined with spaces and passed
+    /// as the function's string input; its string output becomes the result.
+    fn call_wasm_export(&self, function_name: &str, args: &[&str]) -> Result<String> {
+        let (module, allowed_paths, allowed_vars) = match &self.manifest.runtime {
+            PluginRuntime::Wasm { module, allowed_paths, allowed_vars } => (module, allowed_paths, allowed_vars),
+            PluginRuntime::Script | PluginRuntime::Native { .. } | PluginRuntime::Daemon { .. } => {
+                return Err(color_eyre::eyre::eyre!("Plugin '{}' does not declare a WASM runtime", self.manifest.name));
+            }
+        };
+
+        let mut cache = self.wasm_plugin.lock().unwrap();
+        if cache.is_none() {
+            let module_path = self.directory.join(module);
+            let mut manifest = extism::Manifest::new([extism::Wasm::file(&module_path)]);
+
+            for (guest_path, host_path) in allowed_paths {
+                manifest = manifest.with_allowed_path(guest_path.clone(), host_path.as_str());
+            }
+
+            for var in allowed_vars {
+                if let Ok(value) = std::env::var(var) {
+                    manifest = manifest.with_config_key(var.clone(), value);
+                }
+            }
+
+            let instance = extism::Plugin::new(manifest, [], false).map_err(|e| {
+                color_eyre::eyre::eyre!("Failed to instantiate WASM module '{}': {}", module_path.display(), e)
+            })?;
+            *cache = Some(instance);
+        }
+
+        let instance = cache.as_mut().unwrap();
+        let input = args.join(" ");
+        instance.call::<&str, String>(function_name, input.as_str())
+            .map_err(|e| color_eyre::eyre::eyre!("WASM function '{}' failed: {}", function_name, e))
+    }
+}
+
+/// A single capability-catalogue registration: which plugin currently claims it, and
+/// whether that claim was declared `default` (and so wins conflicts with non-default
+/// claims on the same `(kind, name)`).
+#[derive(Debug, Clone)]
+struct CatalogueEntry {
+    plugin_name: String,
+    is_default: bool,
 }
 
 /// Plugin loader for discovering and loading plugins
@@ -282,6 +850,11 @@ impl PluginLoader {
         self.plugin_dirs.push(path.as_ref().to_path_buf());
         self
     }
+
+    /// Directories this loader searches for plugins, in the order they were added
+    pub fn plugin_dirs(&self) -> &[PathBuf] {
+        &self.plugin_dirs
+    }
     
     /// Discover all plugins
     pub fn discover_plugins(&self) -> Result<Vec<Plugin>> {
@@ -351,34 +924,92 @@ impl PluginLoader {
 impl Default for PluginLoader {
     fn default() -> Self {
         let mut loader = PluginLoader::new();
-        
-        // Add default plugin directories
-        if let Some(config_dir) = dirs::config_dir() {
-            loader.add_plugin_dir(config_dir.join("hyprsupreme/plugins"));
-        }
-        
+
+        // Add default plugin directories, in XDG resolution order
+        loader.add_plugin_dir(crate::xdg::Dirs::resolve().plugin_dir());
+
         if let Some(data_dir) = dirs::data_dir() {
             loader.add_plugin_dir(data_dir.join("hyprsupreme/plugins"));
         }
-        
+
         // Add local plugins directory
         loader.add_plugin_dir("./plugins");
-        
+
         loader
     }
 }
 
+/// A plugin registered to handle a named action or filter, in priority order
+#[derive(Debug, Clone)]
+struct HookRegistration {
+    plugin_name: String,
+    priority: i32,
+}
+
+/// One recorded invocation of an action or filter, captured when tracing is enabled
+#[derive(Debug, Clone, Serialize)]
+pub struct HookTraceEntry {
+    /// `"action"` or `"filter"`
+    pub kind: &'static str,
+
+    /// Name of the hook point that was fired
+    pub hook: String,
+
+    /// Plugin that handled this invocation
+    pub plugin: String,
+
+    /// How long the plugin took to handle it
+    pub duration_ms: f64,
+}
+
 /// Plugin manager for handling plugin lifecycle
 #[derive(Debug)]
 pub struct PluginManager {
     /// Plugin loader
     loader: PluginLoader,
-    
+
     /// Loaded plugins
     plugins: HashMap<String, Plugin>,
-    
+
     /// Enabled plugins
     enabled_plugins: Vec<String>,
+
+    /// Action hooks registered by plugins, keyed by action name
+    actions: HashMap<String, Vec<HookRegistration>>,
+
+    /// Filter hooks registered by plugins, keyed by filter name
+    filters: HashMap<String, Vec<HookRegistration>>,
+
+    /// Whether to record hook dispatch timing in `trace_log`
+    tracing_enabled: bool,
+
+    /// Recorded hook invocations, populated only while `tracing_enabled` is set
+    trace_log: Vec<HookTraceEntry>,
+
+    /// `Library` handles and instances for currently-loaded `PluginRuntime::Native`
+    /// plugins, keyed by plugin name. Entries exist only while the plugin is enabled.
+    native_plugins: HashMap<String, LoadedNativePlugin>,
+
+    /// Live child processes for currently-enabled `PluginRuntime::Daemon` plugins,
+    /// keyed by plugin name.
+    daemons: HashMap<String, ChildPluginProcess>,
+
+    /// Capability catalogue: `kind` -> capability name -> the plugin currently serving
+    /// it. Populated from each plugin's manifest `provides` list as it's registered
+    /// (via `initialize`/`discover_plugins`/`install_plugin`) and pruned when that
+    /// plugin is disabled or uninstalled.
+    catalogue: HashMap<String, HashMap<String, CatalogueEntry>>,
+
+    /// Public keys `install_plugin` accepts a plugin's detached signature from,
+    /// populated from user config via `add_trusted_key`.
+    trusted_keys: Vec<VerifyingKey>,
+
+    /// What `enable_plugin` does with a plugin whose `verified` outcome is an `Err`.
+    signature_policy: SignaturePolicy,
+
+    /// Bare `.wasm` files discovered by `scan_wasm_plugins`, the self-describing
+    /// second plugin backend that coexists with `plugins`.
+    wasm_plugins: Vec<WasmModulePlugin>,
 }
 
 impl PluginManager {
@@ -388,41 +1019,250 @@ impl PluginManager {
             loader: PluginLoader::default(),
             plugins: HashMap::new(),
             enabled_plugins: Vec::new(),
+            actions: HashMap::new(),
+            filters: HashMap::new(),
+            tracing_enabled: false,
+            trace_log: Vec::new(),
+            native_plugins: HashMap::new(),
+            daemons: HashMap::new(),
+            catalogue: HashMap::new(),
+            trusted_keys: Vec::new(),
+            signature_policy: SignaturePolicy::default(),
+            wasm_plugins: Vec::new(),
         }
     }
-    
-    /// Initialize the plugin manager
-    pub fn initialize(&mut self) -> Result<()> {
-        // Discover plugins
-        let plugins = self.loader.discover_plugins()?;
-        
-        for plugin in plugins {
-            self.plugins.insert(plugin.manifest.name.clone(), plugin);
+
+    /// Register a plugin to run as an action at the named lifecycle point. Actions are
+    /// side-effecting callbacks; all registered actions run (in priority order, lowest first)
+    /// unless `fire_action` is called in `firstresult` mode.
+    pub fn register_action(&mut self, name: &str, plugin_name: &str, priority: i32) -> Result<()> {
+        if !self.plugins.contains_key(plugin_name) {
+            return Err(PluginError::NotFound(plugin_name.to_string()).into());
         }
-        
+
+        let registrations = self.actions.entry(name.to_string()).or_default();
+        registrations.retain(|r| r.plugin_name != plugin_name);
+        registrations.push(HookRegistration { plugin_name: plugin_name.to_string(), priority });
+        registrations.sort_by_key(|r| r.priority);
+
         Ok(())
     }
-    
-    /// Get the plugin loader
-    pub fn loader(&self) -> &PluginLoader {
-        &self.loader
-    }
-    
-    /// Get a mutable reference to the plugin loader
-    pub fn loader_mut(&mut self) -> &mut PluginLoader {
-        &mut self.loader
-    }
-    
-    /// Get a plugin by name
-    pub fn get_plugin(&self, name: &str) -> Option<&Plugin> {
-        self.plugins.get(name)
-    }
-    
-    /// Get a mutable reference to a plugin by name
-    pub fn get_plugin_mut(&mut self, name: &str) -> Option<&mut Plugin> {
-        self.plugins.get_mut(name)
+
+    /// Register a plugin to run as a filter on the named value. Filters are chained in
+    /// priority order (lowest first), each receiving the previous filter's output.
+    pub fn register_filter(&mut self, name: &str, plugin_name: &str, priority: i32) -> Result<()> {
+        if !self.plugins.contains_key(plugin_name) {
+            return Err(PluginError::NotFound(plugin_name.to_string()).into());
+        }
+
+        let registrations = self.filters.entry(name.to_string()).or_default();
+        registrations.retain(|r| r.plugin_name != plugin_name);
+        registrations.push(HookRegistration { plugin_name: plugin_name.to_string(), priority });
+        registrations.sort_by_key(|r| r.priority);
+
+        Ok(())
     }
-    
+
+    /// Fire an action, running every plugin registered for `name` in priority order and
+    /// passing `context` as its single argument. In `firstresult` mode, stops at and
+    /// returns only the first non-empty result; otherwise collects every result.
+    pub fn fire_action(&mut self, name: &str, context: &str, firstresult: bool) -> Result<Vec<String>> {
+        let registrations = match self.actions.get(name) {
+            Some(registrations) => registrations.clone(),
+            None => return Ok(Vec::new()),
+        };
+
+        let mut results = Vec::new();
+
+        for registration in registrations {
+            if !self.plugins.contains_key(&registration.plugin_name) {
+                continue;
+            }
+
+            let start = std::time::Instant::now();
+            let output = self.dispatch_hook(&registration.plugin_name, name, &[context]);
+            self.record_trace("action", name, &registration.plugin_name, start);
+
+            match output {
+                Ok(result) => {
+                    if firstresult && !result.trim().is_empty() {
+                        return Ok(vec![result]);
+                    }
+                    results.push(result);
+                },
+                Err(err) => {
+                    tracing::warn!("Action '{}' failed for plugin {}: {}", name, registration.plugin_name, err);
+                }
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Apply every filter registered for `name`, in priority order, threading `value`
+    /// through each plugin so each sees the previous one's output.
+    pub fn apply_filters(&mut self, name: &str, value: &str, context: &str) -> Result<String> {
+        let steps = self.apply_filters_steps(name, value, context)?;
+        Ok(steps.into_iter().last().unwrap_or_else(|| value.to_string()))
+    }
+
+    /// Like `apply_filters`, but returns the value produced after *each* filter ran
+    /// (in registration-priority order) instead of only the final result, so callers
+    /// can inspect what each individual plugin contributed.
+    pub fn apply_filters_steps(&mut self, name: &str, value: &str, context: &str) -> Result<Vec<String>> {
+        Ok(self.apply_filters_steps_named(name, value, context)?
+            .into_iter()
+            .map(|(_, step)| step)
+            .collect())
+    }
+
+    /// Like `apply_filters_steps`, but also names the plugin responsible for each step,
+    /// so callers can attribute a given change in the value to the plugin that made it
+    /// (e.g. to tell a legitimate override from a genuine conflict between two plugins).
+    pub fn apply_filters_steps_named(&mut self, name: &str, value: &str, context: &str) -> Result<Vec<(String, String)>> {
+        let registrations = match self.filters.get(name) {
+            Some(registrations) => registrations.clone(),
+            None => return Ok(Vec::new()),
+        };
+
+        let mut current = value.to_string();
+        let mut steps = Vec::new();
+
+        for registration in registrations {
+            if !self.plugins.contains_key(&registration.plugin_name) {
+                continue;
+            }
+
+            let start = std::time::Instant::now();
+            let output = self.dispatch_hook(&registration.plugin_name, name, &[current.as_str(), context]);
+            self.record_trace("filter", name, &registration.plugin_name, start);
+
+            match output {
+                Ok(result) => {
+                    current = result;
+                    steps.push((registration.plugin_name.clone(), current.clone()));
+                },
+                Err(err) => {
+                    tracing::warn!("Filter '{}' failed for plugin {}: {}", name, registration.plugin_name, err);
+                }
+            }
+        }
+
+        Ok(steps)
+    }
+
+    /// Start recording which plugin handled each hook and how long it took
+    pub fn enable_tracing(&mut self) {
+        self.tracing_enabled = true;
+    }
+
+    /// Retrieve the recorded hook trace as structured JSON
+    pub fn trace_log(&self) -> Result<String> {
+        serde_json::to_string(&self.trace_log)
+            .with_context(|| "Failed to serialize hook trace log")
+    }
+
+    fn record_trace(&mut self, kind: &'static str, hook: &str, plugin: &str, start: std::time::Instant) {
+        if !self.tracing_enabled {
+            return;
+        }
+
+        self.trace_log.push(HookTraceEntry {
+            kind,
+            hook: hook.to_string(),
+            plugin: plugin.to_string(),
+            duration_ms: start.elapsed().as_secs_f64() * 1000.0,
+        });
+    }
+    
+    /// Scan each of `paths` for plugin packages (a module directory plus a `plugin.toml`/
+    /// `plugin.json` manifest declaring name, version, hooks/commands, and an entry point)
+    /// and add them to the registry. Rejects manifests with an invalid semver `version` or
+    /// a name that duplicates one already registered, returning the names newly discovered.
+    pub fn discover_plugins<P: AsRef<Path>>(&mut self, paths: &[P]) -> Result<Vec<String>> {
+        let mut discovered = Vec::new();
+
+        for path in paths {
+            let dir = path.as_ref();
+            if !dir.exists() || !dir.is_dir() {
+                continue;
+            }
+
+            for entry in fs::read_dir(dir)
+                .with_context(|| format!("Failed to read plugin directory: {}", dir.display()))? {
+                let entry = entry?;
+                let plugin_dir = entry.path();
+
+                if !plugin_dir.is_dir() {
+                    continue;
+                }
+
+                let manifest_path = ["toml", "json"].iter()
+                    .map(|ext| plugin_dir.join(format!("plugin.{}", ext)))
+                    .find(|path| path.exists());
+
+                let manifest_path = match manifest_path {
+                    Some(path) => path,
+                    None => continue,
+                };
+
+                let manifest = PluginManifest::from_file(&manifest_path)?;
+
+                Version::parse(&manifest.version).with_context(|| {
+                    format!("Invalid semver version for plugin '{}': '{}'", manifest.name, manifest.version)
+                })?;
+
+                if self.plugins.contains_key(&manifest.name) {
+                    return Err(color_eyre::eyre::eyre!("Duplicate plugin name: '{}'", manifest.name));
+                }
+
+                let name = manifest.name.clone();
+                self.plugins.insert(name.clone(), Plugin::new(manifest, plugin_dir));
+                self.register_capabilities(&name)?;
+                discovered.push(name);
+            }
+        }
+
+        Ok(discovered)
+    }
+
+    /// Initialize the plugin manager
+    pub fn initialize(&mut self) -> Result<()> {
+        // Discover plugins
+        let plugins = self.loader.discover_plugins()?;
+
+        let names: Vec<String> = plugins.iter().map(|plugin| plugin.manifest.name.clone()).collect();
+        for plugin in plugins {
+            self.plugins.insert(plugin.manifest.name.clone(), plugin);
+        }
+
+        for name in names {
+            self.register_capabilities(&name)?;
+        }
+
+        Ok(())
+    }
+    
+    /// Get the plugin loader
+    pub fn loader(&self) -> &PluginLoader {
+        &self.loader
+    }
+    
+    /// Get a mutable reference to the plugin loader
+    pub fn loader_mut(&mut self) -> &mut PluginLoader {
+        &mut self.loader
+    }
+    
+    /// Get a plugin by name
+    pub fn get_plugin(&self, name: &str) -> Option<&Plugin> {
+        self.plugins.get(name)
+    }
+    
+    /// Get a mutable reference to a plugin by name
+    pub fn get_plugin_mut(&mut self, name: &str) -> Option<&mut Plugin> {
+        self.plugins.get_mut(name)
+    }
+    
     /// Get all loaded plugins
     pub fn get_all_plugins(&self) -> Vec<&Plugin> {
         self.plugins.values().collect()
@@ -434,92 +1274,334 @@ impl PluginManager {
             .filter_map(|name| self.plugins.get(name))
             .collect()
     }
-    
-    /// Enable a plugin
+
+    /// Names of the enabled plugins, ordered so that every plugin's dependencies
+    /// (enabled or not) precede it. This is the order the hook engine runs
+    /// lifecycle hooks in, so a dependency's pre-build/post-build work always
+    /// happens before its dependents'.
+    pub fn enabled_plugins_in_dependency_order(&self) -> std::result::Result<Vec<String>, PluginError> {
+        let order = self.topological_order()?;
+        Ok(order.into_iter().filter(|name| self.enabled_plugins.contains(name)).collect())
+    }
+
+    /// Enable a plugin, and any of its not-yet-enabled transitive dependencies, in an
+    /// order derived from a topological sort of the whole dependency graph (so a
+    /// dependency is always enabled before anything that needs it). Returns
+    /// `PluginError::MissingDependency`/`VersionMismatch` if a dependency can't be
+    /// satisfied, or `PluginError::DependencyCycle` if the graph isn't a DAG.
     pub fn enable_plugin(&mut self, name: &str) -> Result<()> {
         if !self.plugins.contains_key(name) {
-            return Err(color_eyre::eyre::eyre!("Plugin not found: {}", name));
+            return Err(PluginError::NotFound(name.to_string()).into());
         }
-        
-        // Check if already enabled
+
         if self.enabled_plugins.contains(&name.to_string()) {
             return Ok(());
         }
-        
-        // Check dependencies
-        let dependencies = {
-            let plugin = self.plugins.get(name).unwrap();
-            plugin.manifest.dependencies.clone()
-        };
-        
-        for (dep_name, dep_req) in dependencies {
-            match self.plugins.get(&dep_name) {
-                Some(dep) => {
-                    if !dep.manifest.satisfies_requirement(&dep_req)? {
-                        return Err(color_eyre::eyre::eyre!("Dependency version mismatch: {} requires {} {}", name, dep_name, dep_req));
+
+        let closure = self.dependency_closure(name);
+        for plugin_name in &closure {
+            let plugin = self.plugins.get(plugin_name).unwrap();
+            for (dep_name, dep_req) in &plugin.manifest.dependencies {
+                let dep = self.plugins.get(dep_name).ok_or_else(|| PluginError::MissingDependency {
+                    plugin: plugin_name.clone(),
+                    dependency: dep_name.clone(),
+                })?;
+
+                if !dep.manifest.satisfies_requirement(dep_req)? {
+                    return Err(PluginError::VersionMismatch {
+                        plugin: plugin_name.clone(),
+                        dependency: dep_name.clone(),
+                        requirement: dep_req.clone(),
+                    }.into());
+                }
+            }
+        }
+
+        // Global post-order: dependencies precede dependents. Filtering to `closure`
+        // preserves that relative ordering for just the plugins we're about to enable.
+        let order = self.topological_order()?;
+
+        for plugin_name in order {
+            if !closure.contains(&plugin_name) || self.enabled_plugins.contains(&plugin_name) {
+                continue;
+            }
+
+            if let Err(reason) = self.plugins.get(&plugin_name).unwrap().verified.clone() {
+                match self.signature_policy {
+                    SignaturePolicy::Strict => {
+                        return Err(PluginError::Untrusted { plugin: plugin_name.clone(), reason }.into());
                     }
-                    
-                    // Enable dependency if not already enabled
-                    if !self.enabled_plugins.contains(&dep_name) {
-                        self.enable_plugin(&dep_name)?;
+                    SignaturePolicy::Warn => {
+                        tracing::warn!("Enabling unverified plugin '{}': {}", plugin_name, reason);
                     }
-                },
-                None => {
-                    return Err(color_eyre::eyre::eyre!("Missing dependency: {} requires {}", name, dep_name));
                 }
             }
+
+            let runtime = self.plugins.get(&plugin_name).unwrap().manifest.runtime.clone();
+            let directory = self.plugins.get(&plugin_name).unwrap().directory.clone();
+
+            match &runtime {
+                // If this is a native plugin, dlopen its library and run the load/enable hooks
+                PluginRuntime::Native { library } => {
+                    let lib_path = directory.join(library);
+                    self.load_native_plugin(&plugin_name, &lib_path)?;
+                }
+                // If this is a daemon plugin, check protocol compatibility and spawn its
+                // long-lived child process
+                PluginRuntime::Daemon { entry, protocol_version } => {
+                    if !versions_compatible(HOST_PROTOCOL_VERSION, protocol_version)? {
+                        return Err(PluginError::ProtocolIncompatible {
+                            plugin: plugin_name.clone(),
+                            host_version: HOST_PROTOCOL_VERSION.to_string(),
+                            requirement: protocol_version.clone(),
+                        }.into());
+                    }
+
+                    let entry_path = directory.join(entry);
+                    let daemon = ChildPluginProcess::spawn(&entry_path, &directory)?;
+                    self.daemons.insert(plugin_name.clone(), daemon);
+                }
+                PluginRuntime::Script | PluginRuntime::Wasm { .. } => {}
+            }
+
+            if let Some(plugin) = self.plugins.get_mut(&plugin_name) {
+                plugin.state = PluginState::Enabled;
+            }
+
+            // Re-claim this plugin's capabilities now that it's live again (a prior
+            // `disable_plugin` would have pruned them from the catalogue).
+            self.register_capabilities(&plugin_name)?;
+
+            self.enabled_plugins.push(plugin_name);
         }
-        
-        // Set state
-        if let Some(plugin) = self.plugins.get_mut(name) {
-            plugin.state = PluginState::Enabled;
-        }
-        
-        // Add to enabled plugins
-        self.enabled_plugins.push(name.to_string());
-        
+
         Ok(())
     }
-    
-    /// Disable a plugin
+
+    /// Disable a plugin and every enabled plugin that transitively depends on it (but
+    /// not its own dependencies, which other enabled plugins may still need), in the
+    /// reverse of the same topological order `enable_plugin` uses -- so dependents are
+    /// torn down before the plugin they depend on.
     pub fn disable_plugin(&mut self, name: &str) -> Result<()> {
         if !self.plugins.contains_key(name) {
-            return Err(color_eyre::eyre::eyre!("Plugin not found: {}", name));
+            return Err(PluginError::NotFound(name.to_string()).into());
         }
-        
-        // Check if already disabled
+
         if !self.enabled_plugins.contains(&name.to_string()) {
             return Ok(());
         }
-        
-        // Check for dependent plugins
-        let dependent_plugins: Vec<String> = self.plugins.iter()
-            .filter(|(_, plugin)| {
-                plugin.manifest.dependencies.contains_key(name)
-            })
-            .map(|(dep_name, _)| dep_name.clone())
-            .collect();
-        
-        // Disable dependent plugins first
-        for dep_name in dependent_plugins {
-            self.disable_plugin(&dep_name)?;
+
+        let dependents = self.transitive_dependents(name);
+        let mut order = self.topological_order()?;
+        order.reverse();
+
+        for plugin_name in order {
+            if !dependents.contains(&plugin_name) || !self.enabled_plugins.contains(&plugin_name) {
+                continue;
+            }
+
+            if let Some(plugin) = self.plugins.get_mut(&plugin_name) {
+                plugin.state = PluginState::Installed;
+            }
+
+            self.enabled_plugins.retain(|n| n != &plugin_name);
+
+            // A disabled plugin's hooks aren't invokable, so it shouldn't be routed to
+            // as a capability provider until it's re-enabled.
+            self.unregister_capabilities(&plugin_name);
+
+            // Run on_disable, then on_unload, and drop the library before the plugin is
+            // considered fully disabled
+            self.disable_native_plugin(&plugin_name);
+            self.unload_native_plugin(&plugin_name);
+
+            // Ask the daemon (if any) to shut down before the plugin is considered disabled
+            self.shutdown_daemon(&plugin_name);
         }
-        
-        // Set state
-        if let Some(plugin) = self.plugins.get_mut(name) {
-            plugin.state = PluginState::Installed;
+
+        Ok(())
+    }
+
+    /// Plugin names that `name` transitively depends on, including `name` itself.
+    fn dependency_closure(&self, name: &str) -> std::collections::HashSet<String> {
+        let mut seen = std::collections::HashSet::new();
+        let mut stack = vec![name.to_string()];
+
+        while let Some(current) = stack.pop() {
+            if !seen.insert(current.clone()) {
+                continue;
+            }
+
+            if let Some(plugin) = self.plugins.get(&current) {
+                stack.extend(plugin.manifest.dependencies.keys().cloned());
+            }
         }
-        
-        // Remove from enabled plugins
-        self.enabled_plugins.retain(|n| n != name);
-        
+
+        seen
+    }
+
+    /// Plugin names that transitively depend on `name`, including `name` itself.
+    fn transitive_dependents(&self, name: &str) -> std::collections::HashSet<String> {
+        let mut seen = std::collections::HashSet::new();
+        let mut stack = vec![name.to_string()];
+
+        while let Some(current) = stack.pop() {
+            if !seen.insert(current.clone()) {
+                continue;
+            }
+
+            for (plugin_name, plugin) in &self.plugins {
+                if plugin.manifest.dependencies.contains_key(&current) {
+                    stack.push(plugin_name.clone());
+                }
+            }
+        }
+
+        seen
+    }
+
+    /// Topologically sort the full plugin dependency graph via depth-first traversal
+    /// with three-color marking (white = unvisited, gray = on the current stack,
+    /// black = finished). Nodes are pushed onto the result in post-order, so every
+    /// plugin's dependencies appear before it. Reaching a gray node is a back edge --
+    /// a dependency cycle -- reported as `PluginError::DependencyCycle` with the path
+    /// that closed it.
+    fn topological_order(&self) -> std::result::Result<Vec<String>, PluginError> {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum Mark {
+            White,
+            Gray,
+            Black,
+        }
+
+        fn visit(
+            name: &str,
+            plugins: &HashMap<String, Plugin>,
+            marks: &mut HashMap<String, Mark>,
+            path: &mut Vec<String>,
+            order: &mut Vec<String>,
+        ) -> std::result::Result<(), PluginError> {
+            match marks.get(name).copied().unwrap_or(Mark::White) {
+                Mark::Black => return Ok(()),
+                Mark::Gray => {
+                    let mut cycle = path.clone();
+                    cycle.push(name.to_string());
+                    return Err(PluginError::DependencyCycle(cycle));
+                }
+                Mark::White => {}
+            }
+
+            marks.insert(name.to_string(), Mark::Gray);
+            path.push(name.to_string());
+
+            if let Some(plugin) = plugins.get(name) {
+                for dep_name in plugin.manifest.dependencies.keys() {
+                    if plugins.contains_key(dep_name) {
+                        visit(dep_name, plugins, marks, path, order)?;
+                    }
+                }
+            }
+
+            path.pop();
+            marks.insert(name.to_string(), Mark::Black);
+            order.push(name.to_string());
+
+            Ok(())
+        }
+
+        let mut marks = HashMap::new();
+        let mut path = Vec::new();
+        let mut order = Vec::new();
+
+        let mut names: Vec<&String> = self.plugins.keys().collect();
+        names.sort();
+
+        for name in names {
+            visit(name, &self.plugins, &mut marks, &mut path, &mut order)?;
+        }
+
+        Ok(order)
+    }
+
+    /// `dlopen` a native plugin's shared library, resolve its
+    /// `_hyprsupreme_plugin_create` constructor, and run `on_load`/`on_enable` on the
+    /// resulting instance. The `Library` handle is kept in `native_plugins` for as long
+    /// as the plugin stays enabled.
+    fn load_native_plugin(&mut self, name: &str, lib_path: &Path) -> Result<()> {
+        if self.native_plugins.contains_key(name) {
+            return Ok(());
+        }
+
+        // Safety: loading and calling into third-party native plugin code is inherently
+        // unsafe; we trust the manifest's declared `library` path and constructor symbol.
+        unsafe {
+            let library = Library::new(lib_path)
+                .with_context(|| format!("Failed to load native plugin library: {}", lib_path.display()))?;
+
+            let constructor: Symbol<unsafe extern "C" fn() -> *mut dyn NativePlugin> = library
+                .get(NATIVE_PLUGIN_CONSTRUCTOR_SYMBOL)
+                .with_context(|| format!(
+                    "Native plugin library missing `_hyprsupreme_plugin_create` symbol: {}",
+                    lib_path.display()
+                ))?;
+
+            let raw = constructor();
+            if raw.is_null() {
+                return Err(color_eyre::eyre::eyre!(
+                    "Native plugin constructor returned a null pointer: {}",
+                    lib_path.display()
+                ));
+            }
+
+            let mut instance = Box::from_raw(raw);
+            instance.on_load();
+            instance.on_enable();
+
+            self.native_plugins.insert(name.to_string(), LoadedNativePlugin { instance, _library: library });
+        }
+
         Ok(())
     }
+
+    /// Run `on_unload` on a loaded native plugin and then drop its `Library` handle, in
+    /// that order -- dropping the library first would unmap the plugin's code while its
+    /// own destructor still needs to run, which is undefined behavior. A no-op if `name`
+    /// has no loaded native plugin.
+    fn unload_native_plugin(&mut self, name: &str) {
+        if let Some(LoadedNativePlugin { mut instance, _library }) = self.native_plugins.remove(name) {
+            instance.on_unload();
+            drop(instance);
+            drop(_library);
+        }
+    }
+
+    /// Run `on_disable` for a currently-loaded native plugin, without unloading it --
+    /// called from `disable_plugin` before `unload_native_plugin` so the documented
+    /// `on_load` -> `on_enable` -> `on_disable` -> `on_unload` lifecycle is actually driven.
+    fn disable_native_plugin(&mut self, name: &str) {
+        if let Some(LoadedNativePlugin { instance, .. }) = self.native_plugins.get_mut(name) {
+            instance.on_disable();
+        }
+    }
+
+    /// Run `on_unload` and drop the library for every currently-loaded native plugin.
+    /// Callers should invoke this before the process exits so native plugins get a
+    /// chance to clean up before their code is unmapped.
+    pub fn unload_all(&mut self) {
+        let names: Vec<String> = self.native_plugins.keys().cloned().collect();
+        for name in names {
+            self.unload_native_plugin(&name);
+        }
+    }
     
-    /// Install a plugin from a directory
+    /// Install a plugin from a directory, running its `preinst` lifecycle script (if
+    /// any) before copying files and `postinst` after. The operation passed to those
+    /// scripts is `Upgrade` if a plugin is already installed at the target directory,
+    /// `Install` otherwise. If `postinst` fails, the partially-copied target directory
+    /// is removed so a failed install doesn't leave a half-installed plugin behind.
     pub fn install_plugin<P: AsRef<Path>>(&mut self, source_dir: P) -> Result<()> {
         let source_dir = source_dir.as_ref();
-        
+
         // Find manifest
         let mut manifest_path = None;
         for ext in &["toml", "json"] {
@@ -529,132 +1611,508 @@ impl PluginManager {
                 break;
             }
         }
-        
+
         let manifest_path = manifest_path
             .ok_or_else(|| color_eyre::eyre::eyre!("Plugin manifest not found in: {}", source_dir.display()))?;
-        
+
         // Load manifest
         let manifest = PluginManifest::from_file(&manifest_path)?;
-        
-        // Determine target directory
-        let target_dir = if let Some(config_dir) = dirs::config_dir() {
-            let plugins_dir = config_dir.join("hyprsupreme/plugins");
-            
-            // Create directory if it doesn't exist
-            if !plugins_dir.exists() {
-                fs::create_dir_all(&plugins_dir)
-                    .with_context(|| format!("Failed to create plugins directory: {}", plugins_dir.display()))?;
-            }
-            
-            plugins_dir.join(&manifest.name)
+
+        // Determine target directory, under the resolved XDG plugin directory
+        let plugins_dir = crate::xdg::Dirs::resolve().plugin_dir();
+        if !plugins_dir.exists() {
+            fs::create_dir_all(&plugins_dir)
+                .with_context(|| format!("Failed to create plugins directory: {}", plugins_dir.display()))?;
+        }
+        let target_dir = plugins_dir.join(&manifest.name);
+
+        let operation = if target_dir.exists() {
+            InstallOperation::Upgrade
         } else {
-            return Err(color_eyre::eyre::eyre!("Could not determine config directory"));
+            InstallOperation::Install
         };
-        
-        // Check if already installed
-        if target_dir.exists() {
-            return Err(color_eyre::eyre::eyre!("Plugin already installed: {}", manifest.name));
+
+        // Verify the detached signature (if any) over the manifest and its declared
+        // scripts/WASM/library/entry file before copying anything into place. Unsigned
+        // plugins and policy enforcement are handled at enable time, not here --
+        // installing always records the outcome, enabling is what's gated.
+        let signature_path = source_dir.join("plugin.sig");
+        let verified = if signature_path.exists() {
+            self.verify_plugin_signature(&manifest, source_dir, &signature_path)
+                .map_err(|err| err.to_string())
+        } else {
+            Err("plugin ships no signature file".to_string())
+        };
+
+        if let Err(reason) = &verified {
+            tracing::warn!("Plugin '{}' signature not verified: {}", manifest.name, reason);
         }
-        
-        // Copy plugin files
+
+        Self::run_lifecycle_script(&manifest, source_dir, "preinst", operation)?;
+
+        if operation == InstallOperation::Upgrade {
+            fs::remove_dir_all(&target_dir)
+                .with_context(|| format!("Failed to remove existing installation: {}", target_dir.display()))?;
+        }
+
+        // Copy plugin files into target_dir itself (content_only, so the source
+        // directory's own basename -- which need not match manifest.name -- doesn't
+        // become an extra path component under it)
+        fs::create_dir_all(&target_dir)
+            .with_context(|| format!("Failed to create plugin directory: {}", target_dir.display()))?;
         fs_extra::dir::copy(
             source_dir,
-            target_dir.parent().unwrap(),
-            &fs_extra::dir::CopyOptions::new().content_only(false),
+            &target_dir,
+            &fs_extra::dir::CopyOptions::new().content_only(true),
         ).with_context(|| format!("Failed to copy plugin files from {} to {}", source_dir.display(), target_dir.display()))?;
-        
+
+        if let Err(err) = Self::run_lifecycle_script(&manifest, &target_dir, "postinst", operation) {
+            let _ = fs::remove_dir_all(&target_dir);
+            return Err(err);
+        }
+
         // Load the plugin
-        let plugin = Plugin::new(manifest, target_dir);
-        self.plugins.insert(plugin.manifest.name.clone(), plugin);
-        
+        let mut plugin = Plugin::new(manifest, target_dir);
+        plugin.verified = verified;
+        let name = plugin.manifest.name.clone();
+        self.plugins.insert(name.clone(), plugin);
+        self.register_capabilities(&name)?;
+
         Ok(())
     }
-    
-    /// Uninstall a plugin
+
+    /// Uninstall a plugin, running its `prerm` lifecycle script before the directory is
+    /// removed and `postrm` after. Since `postrm` can't run from inside the directory
+    /// being deleted, its script is copied out to a temporary file first.
     pub fn uninstall_plugin(&mut self, name: &str) -> Result<()> {
         if !self.plugins.contains_key(name) {
-            return Err(color_eyre::eyre::eyre!("Plugin not found: {}", name));
+            return Err(PluginError::NotFound(name.to_string()).into());
         }
-        
+
+        // Refuse to remove a plugin another registered plugin still declares as a
+        // dependency, even if that dependent is currently disabled.
+        if let Some((dependent, _)) = self.plugins.iter()
+            .find(|(plugin_name, plugin)| plugin_name.as_str() != name && plugin.manifest.dependencies.contains_key(name))
+        {
+            return Err(PluginError::InUseBy { plugin: name.to_string(), dependent: dependent.clone() }.into());
+        }
+
         // Disable the plugin first
         if self.enabled_plugins.contains(&name.to_string()) {
             self.disable_plugin(name)?;
         }
-        
-        // Get plugin directory
-        let dir = {
+
+        // Get plugin directory and manifest
+        let (dir, manifest) = {
             let plugin = self.plugins.get(name).unwrap();
-            plugin.directory.clone()
+            (plugin.directory.clone(), plugin.manifest.clone())
         };
-        
+
+        Self::run_lifecycle_script(&manifest, &dir, "prerm", InstallOperation::Install)?;
+
+        // `postrm` has to outlive the directory it's declared in, so snapshot it to a
+        // temp file before removal if one is declared.
+        let postrm_snapshot = manifest.lifecycle.postrm.as_ref().and_then(|script| {
+            let script_path = dir.join(script);
+            if !script_path.exists() {
+                return None;
+            }
+            let tmp_path = std::env::temp_dir().join(format!("hyprsupreme-postrm-{}-{}", name, std::process::id()));
+            fs::copy(&script_path, &tmp_path).ok().map(|_| tmp_path)
+        });
+
         // Remove plugin directory
         if dir.exists() {
             fs::remove_dir_all(&dir)
                 .with_context(|| format!("Failed to remove plugin directory: {}", dir.display()))?;
         }
-        
-        // Remove from plugins map
+
+        // Remove from plugins map and the capability catalogue (a plugin claims its
+        // capabilities when it is loaded or installed, even if it was never enabled)
         self.plugins.remove(name);
-        
+        self.unregister_capabilities(name);
+
+        if let Some(tmp_path) = postrm_snapshot {
+            let result = Command::new(&tmp_path)
+                .arg(InstallOperation::Install.as_arg())
+                .current_dir(std::env::temp_dir())
+                .output();
+
+            let _ = fs::remove_file(&tmp_path);
+
+            let output = result.with_context(|| format!("Failed to execute postrm script for plugin '{}'", name))?;
+            if !output.status.success() {
+                let error = String::from_utf8_lossy(&output.stderr).to_string();
+                return Err(color_eyre::eyre::eyre!("postrm script failed for plugin '{}': {}", name, error));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Run a named lifecycle script (`preinst`/`postinst`/`prerm`/`postrm`) declared on
+    /// `manifest`, if any, resolving it relative to and executing it in `cwd`. A no-op
+    /// if the manifest doesn't declare that script.
+    fn run_lifecycle_script(
+        manifest: &PluginManifest,
+        cwd: &Path,
+        script_name: &str,
+        operation: InstallOperation,
+    ) -> Result<()> {
+        let script = match script_name {
+            "preinst" => manifest.lifecycle.preinst.as_deref(),
+            "postinst" => manifest.lifecycle.postinst.as_deref(),
+            "prerm" => manifest.lifecycle.prerm.as_deref(),
+            "postrm" => manifest.lifecycle.postrm.as_deref(),
+            _ => None,
+        };
+
+        let script = match script {
+            Some(script) => script,
+            None => return Ok(()),
+        };
+
+        let script_path = cwd.join(script);
+        if !script_path.exists() {
+            return Err(color_eyre::eyre::eyre!("Lifecycle script not found: {}", script_path.display()));
+        }
+
+        let output = Command::new(&script_path)
+            .arg(operation.as_arg())
+            .current_dir(cwd)
+            .output()
+            .with_context(|| format!("Failed to execute {} script: {}", script_name, script_path.display()))?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr).to_string();
+            return Err(color_eyre::eyre::eyre!(
+                "{} script failed for plugin '{}': {}", script_name, manifest.name, error
+            ));
+        }
+
         Ok(())
     }
     
     /// Execute a command from a specific plugin
-    pub fn execute_command(&self, plugin_name: &str, command_name: &str, args: &[&str]) -> Result<String> {
-        let plugin = self.get_plugin(plugin_name)
-            .ok_or_else(|| color_eyre::eyre::eyre!("Plugin not found: {}", plugin_name))?;
-        
+    pub fn execute_command(&mut self, plugin_name: &str, command_name: &str, args: &[&str]) -> Result<String> {
+        if !self.plugins.contains_key(plugin_name) {
+            return Err(PluginError::NotFound(plugin_name.to_string()).into());
+        }
+
         if !self.enabled_plugins.contains(&plugin_name.to_string()) {
             return Err(color_eyre::eyre::eyre!("Plugin not enabled: {}", plugin_name));
         }
-        
-        plugin.execute_command(command_name, args)
+
+        self.dispatch_command(plugin_name, command_name, args)
     }
-    
+
     /// Get list of plugin names
     pub fn get_plugins(&self) -> Vec<String> {
         self.plugins.keys().cloned().collect()
     }
-    
-    /// Execute a hook for all enabled plugins
-    pub fn execute_hook(&self, hook_name: &str, args: &[&str]) -> Result<HashMap<String, String>> {
+
+    /// Execute a hook for all enabled plugins, in hook-priority order
+    pub fn execute_hook(&mut self, hook_name: &str, args: &[&str]) -> Result<HashMap<String, String>> {
         let mut results = HashMap::new();
-        
-        // Get all enabled plugins with the hook
-        let plugins_with_hook: Vec<&Plugin> = self.get_enabled_plugins().into_iter()
-            .filter(|plugin| plugin.manifest.hooks.iter().any(|h| h.name == hook_name))
+
+        let mut plugins_with_hook: Vec<(String, i32)> = self.get_enabled_plugins().into_iter()
+            .filter_map(|plugin| {
+                plugin.manifest.hooks.iter()
+                    .find(|h| h.name == hook_name)
+                    .map(|h| (plugin.manifest.name.clone(), h.priority))
+            })
             .collect();
-        
-        // Sort by priority
-        let mut plugins_sorted = plugins_with_hook;
-        plugins_sorted.sort_by(|a, b| {
-            let a_priority = a.manifest.hooks.iter()
-                .find(|h| h.name == hook_name)
-                .map(|h| h.priority)
-                .unwrap_or(0);
-            
-            let b_priority = b.manifest.hooks.iter()
-                .find(|h| h.name == hook_name)
-                .map(|h| h.priority)
-                .unwrap_or(0);
-            
-            a_priority.cmp(&b_priority)
-        });
-        
-        // Execute hooks
-        for plugin in plugins_sorted {
-            match plugin.execute_hook(hook_name, args) {
+
+        plugins_with_hook.sort_by_key(|(_, priority)| *priority);
+
+        for (plugin_name, _) in plugins_with_hook {
+            match self.dispatch_hook(&plugin_name, hook_name, args) {
                 Ok(output) => {
-                    results.insert(plugin.manifest.name.clone(), output);
+                    results.insert(plugin_name, output);
                 },
                 Err(err) => {
-                    tracing::warn!("Failed to execute hook {} for plugin {}: {}", hook_name, plugin.manifest.name, err);
+                    tracing::warn!("Failed to execute hook {} for plugin {}: {}", hook_name, plugin_name, err);
                 }
             }
         }
-        
+
         Ok(results)
     }
+
+    /// Route a hook invocation to the right backend for `plugin_name`'s runtime: a live
+    /// JSON-RPC call for `PluginRuntime::Daemon` plugins (restarting the daemon first if
+    /// it crashed), or the script/WASM path otherwise.
+    fn dispatch_hook(&mut self, plugin_name: &str, hook_name: &str, args: &[&str]) -> Result<String> {
+        let is_daemon = matches!(
+            self.plugins.get(plugin_name).map(|p| &p.manifest.runtime),
+            Some(PluginRuntime::Daemon { .. })
+        );
+
+        if is_daemon {
+            self.ensure_daemon_alive(plugin_name)?;
+            let daemon = self.daemons.get_mut(plugin_name)
+                .ok_or_else(|| color_eyre::eyre::eyre!("Plugin daemon not running: {}", plugin_name))?;
+            return daemon.call(hook_name, args);
+        }
+
+        let plugin = self.plugins.get(plugin_name)
+            .ok_or_else(|| PluginError::NotFound(plugin_name.to_string()))?;
+        plugin.execute_hook(hook_name, args)
+    }
+
+    /// Route a command invocation to the right backend for `plugin_name`'s runtime,
+    /// analogous to `dispatch_hook`.
+    fn dispatch_command(&mut self, plugin_name: &str, command_name: &str, args: &[&str]) -> Result<String> {
+        let is_daemon = matches!(
+            self.plugins.get(plugin_name).map(|p| &p.manifest.runtime),
+            Some(PluginRuntime::Daemon { .. })
+        );
+
+        if is_daemon {
+            self.ensure_daemon_alive(plugin_name)?;
+            let daemon = self.daemons.get_mut(plugin_name)
+                .ok_or_else(|| color_eyre::eyre::eyre!("Plugin daemon not running: {}", plugin_name))?;
+            return daemon.call(command_name, args);
+        }
+
+        let plugin = self.plugins.get(plugin_name)
+            .ok_or_else(|| PluginError::NotFound(plugin_name.to_string()))?;
+        plugin.execute_command(command_name, args)
+    }
+
+    /// Health check run before every daemon dispatch: if `name`'s daemon process has
+    /// exited (crashed or was never started), respawn it from the manifest's declared
+    /// entry script. A no-op for non-daemon plugins.
+    fn ensure_daemon_alive(&mut self, name: &str) -> Result<()> {
+        let needs_restart = match self.daemons.get_mut(name) {
+            Some(daemon) => !daemon.is_alive(),
+            None => true,
+        };
+
+        if !needs_restart {
+            return Ok(());
+        }
+
+        self.daemons.remove(name);
+
+        let plugin = self.plugins.get(name).ok_or_else(|| PluginError::NotFound(name.to_string()))?;
+        let entry = match &plugin.manifest.runtime {
+            PluginRuntime::Daemon { entry, .. } => entry.clone(),
+            _ => return Ok(()),
+        };
+
+        let entry_path = plugin.directory.join(&entry);
+        let daemon = ChildPluginProcess::spawn(&entry_path, &plugin.directory)?;
+        self.daemons.insert(name.to_string(), daemon);
+
+        Ok(())
+    }
+
+    /// Ask a loaded daemon to shut down (see `ChildPluginProcess::shutdown`) and drop
+    /// its handle. A no-op if `name` has no live daemon.
+    fn shutdown_daemon(&mut self, name: &str) {
+        if let Some(daemon) = self.daemons.remove(name) {
+            let _ = daemon.shutdown(std::time::Duration::from_secs(5));
+        }
+    }
+
+    /// Look up the plugin that claims capability `name` under `kind`, e.g.
+    /// `provider_for("theme", "catppuccin")`. `None` if nothing claims it.
+    pub fn provider_for(&self, kind: &str, name: &str) -> Option<&Plugin> {
+        let entry = self.catalogue.get(kind)?.get(name)?;
+        self.plugins.get(&entry.plugin_name)
+    }
+
+    /// Look up the plugin that claims the `"filetype"` capability for `extension`
+    /// (without the leading dot), e.g. `provider_by_extension("kdl")`.
+    pub fn provider_by_extension(&self, extension: &str) -> Option<&Plugin> {
+        self.provider_for("filetype", extension)
+    }
+
+    /// Claim `plugin_name`'s manifest-declared capabilities in the catalogue. If a
+    /// capability's `(kind, name)` is already claimed by another plugin, a declared
+    /// `default` wins over a non-default claim; two claims that agree on `default`
+    /// (both set or both unset) are a `PluginError::CapabilityConflict`. Re-registering
+    /// the same plugin (e.g. on re-enable) simply refreshes its entries.
+    fn register_capabilities(&mut self, plugin_name: &str) -> Result<()> {
+        let capabilities = match self.plugins.get(plugin_name) {
+            Some(plugin) => plugin.manifest.provides.clone(),
+            None => return Ok(()),
+        };
+
+        for capability in capabilities {
+            let by_name = self.catalogue.entry(capability.kind.clone()).or_default();
+
+            let claim = CatalogueEntry {
+                plugin_name: plugin_name.to_string(),
+                is_default: capability.default,
+            };
+
+            match by_name.get(&capability.name) {
+                None => {
+                    by_name.insert(capability.name.clone(), claim);
+                }
+                Some(existing) if existing.plugin_name == plugin_name => {
+                    by_name.insert(capability.name.clone(), claim);
+                }
+                Some(existing) if claim.is_default && !existing.is_default => {
+                    by_name.insert(capability.name.clone(), claim);
+                }
+                Some(existing) if existing.is_default && !claim.is_default => {
+                    // existing default claim wins; leave it in place
+                }
+                Some(existing) => {
+                    return Err(PluginError::CapabilityConflict {
+                        kind: capability.kind.clone(),
+                        name: capability.name.clone(),
+                        first: existing.plugin_name.clone(),
+                        second: plugin_name.to_string(),
+                    }.into());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remove every catalogue entry claimed by `plugin_name`.
+    fn unregister_capabilities(&mut self, plugin_name: &str) {
+        for by_name in self.catalogue.values_mut() {
+            by_name.retain(|_, entry| entry.plugin_name != plugin_name);
+        }
+    }
+
+    /// Trust signatures from `key_hex` (a hex-encoded ed25519 public key, as distributed
+    /// in user config) going forward.
+    pub fn add_trusted_key(&mut self, key_hex: &str) -> Result<()> {
+        let bytes = hex::decode(key_hex.trim())
+            .with_context(|| "Invalid trusted key encoding: expected hex")?;
+        let bytes: [u8; 32] = bytes.try_into()
+            .map_err(|_| color_eyre::eyre::eyre!("Trusted key must be 32 bytes"))?;
+        let key = VerifyingKey::from_bytes(&bytes)
+            .with_context(|| "Invalid ed25519 trusted key")?;
+
+        self.trusted_keys.push(key);
+        Ok(())
+    }
+
+    /// Set the policy `enable_plugin` applies to plugins whose signature didn't verify.
+    pub fn set_signature_policy(&mut self, policy: SignaturePolicy) {
+        self.signature_policy = policy;
+    }
+
+    /// Verify `signature_path` (a hex-encoded ed25519 signature over `compute_plugin_digest`)
+    /// against every trusted key, succeeding as soon as one matches.
+    fn verify_plugin_signature(&self, manifest: &PluginManifest, dir: &Path, signature_path: &Path) -> Result<()> {
+        let digest = compute_plugin_digest(manifest, dir)?;
+
+        let signature_hex = fs::read_to_string(signature_path)
+            .with_context(|| format!("Failed to read signature file: {}", signature_path.display()))?;
+        let signature_bytes = hex::decode(signature_hex.trim())
+            .with_context(|| format!("Invalid signature encoding: {}", signature_path.display()))?;
+        let signature = Signature::from_slice(&signature_bytes)
+            .with_context(|| format!("Malformed signature: {}", signature_path.display()))?;
+
+        let trusted = self.trusted_keys.iter().any(|key| key.verify(&digest, &signature).is_ok());
+        if trusted {
+            Ok(())
+        } else {
+            Err(color_eyre::eyre::eyre!(
+                "Signature for plugin '{}' does not match any trusted key", manifest.name
+            ))
+        }
+    }
+
+    /// Scan `dir` for bare `.wasm` files and instantiate each as a self-describing
+    /// `WasmModulePlugin`, appending to whatever `scan_wasm_plugins`/`scan_all_wasm_plugins`
+    /// have already found. A no-op if `dir` doesn't exist. Failures to instantiate or
+    /// to retrieve `plugin_info` are recorded as `WasmModulePlugin::Failed` rather than
+    /// aborting the scan.
+    pub fn scan_wasm_plugins<P: AsRef<Path>>(&mut self, dir: P) -> Result<()> {
+        let dir = dir.as_ref();
+        if !dir.exists() || !dir.is_dir() {
+            return Ok(());
+        }
+
+        for entry in fs::read_dir(dir)
+            .with_context(|| format!("Failed to read WASM plugin directory: {}", dir.display()))? {
+            let entry = entry?;
+            let path = entry.path();
+
+            if path.extension().and_then(|ext| ext.to_str()) != Some("wasm") {
+                continue;
+            }
+
+            self.wasm_plugins.push(Self::load_wasm_module(&path));
+        }
+
+        Ok(())
+    }
+
+    /// Scan every directory the manager's `PluginLoader` knows about for bare `.wasm`
+    /// plugin files, alongside the directory+manifest plugins `initialize`/
+    /// `discover_plugins` load from those same directories.
+    pub fn scan_all_wasm_plugins(&mut self) -> Result<()> {
+        let dirs: Vec<PathBuf> = self.loader.plugin_dirs().to_vec();
+        for dir in dirs {
+            self.scan_wasm_plugins(&dir)?;
+        }
+        Ok(())
+    }
+
+    fn load_wasm_module(path: &Path) -> WasmModulePlugin {
+        let manifest = extism::Manifest::new([extism::Wasm::file(path)]);
+        let mut instance = match extism::Plugin::new(manifest, [], false) {
+            Ok(instance) => instance,
+            Err(err) => return WasmModulePlugin::Failed { path: path.to_path_buf(), error: err.to_string() },
+        };
+
+        let info_json = match instance.call::<&str, String>("plugin_info", "") {
+            Ok(output) => output,
+            Err(err) => return WasmModulePlugin::Failed {
+                path: path.to_path_buf(),
+                error: format!("'plugin_info' export failed: {}", err),
+            },
+        };
+
+        let info: PluginManifest = match serde_json::from_str(&info_json) {
+            Ok(info) => info,
+            Err(err) => return WasmModulePlugin::Failed {
+                path: path.to_path_buf(),
+                error: format!("'plugin_info' did not return a valid plugin manifest: {}", err),
+            },
+        };
+
+        WasmModulePlugin::Initialized {
+            path: path.to_path_buf(),
+            info,
+            instance: Mutex::new(instance),
+            verified: Ok(()),
+        }
+    }
+
+    /// Every `.wasm`-file-backed plugin discovered so far, successful or not
+    pub fn wasm_plugins(&self) -> &[WasmModulePlugin] {
+        &self.wasm_plugins
+    }
+
+    /// Invoke `export_name` on the WASM module at `path` (as found by `scan_wasm_plugins`),
+    /// passing `input` as its string argument and returning its string output.
+    pub fn execute_wasm_module(&self, path: &Path, export_name: &str, input: &str) -> Result<String> {
+        let module = self.wasm_plugins.iter().find(|module| module.path() == path)
+            .ok_or_else(|| color_eyre::eyre::eyre!("WASM plugin not loaded: {}", path.display()))?;
+
+        match module {
+            WasmModulePlugin::Initialized { instance, .. } => {
+                let mut instance = instance.lock().unwrap();
+                instance.call::<&str, String>(export_name, input)
+                    .map_err(|err| color_eyre::eyre::eyre!("WASM export '{}' failed: {}", export_name, err))
+            }
+            WasmModulePlugin::Failed { error, .. } => {
+                Err(color_eyre::eyre::eyre!("WASM plugin failed to load: {}", error))
+            }
+        }
+    }
 }
 
 impl Default for PluginManager {
@@ -663,6 +2121,16 @@ impl Default for PluginManager {
             loader: PluginLoader::new(),
             plugins: HashMap::new(),
             enabled_plugins: Vec::new(),
+            actions: HashMap::new(),
+            filters: HashMap::new(),
+            tracing_enabled: false,
+            trace_log: Vec::new(),
+            native_plugins: HashMap::new(),
+            daemons: HashMap::new(),
+            catalogue: HashMap::new(),
+            trusted_keys: Vec::new(),
+            signature_policy: SignaturePolicy::default(),
+            wasm_plugins: Vec::new(),
         }
     }
 }
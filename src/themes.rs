@@ -1,6 +1,6 @@
 use color_eyre::{eyre::Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
@@ -24,9 +24,9 @@ pub struct Theme {
     #[serde(default = "default_version")]
     pub version: String,
     
-    /// Base theme to extend (if any)
+    /// Base theme(s) to extend (if any); a single name or a list, applied in order
     #[serde(default)]
-    pub extends: Option<String>,
+    pub extends: Option<Extends>,
     
     /// Color scheme variables
     #[serde(default)]
@@ -39,12 +39,140 @@ pub struct Theme {
     /// Metadata for the theme
     #[serde(default)]
     pub metadata: HashMap<String, String>,
+
+    /// Light/dark appearance this variant targets, if part of a theme family
+    #[serde(default)]
+    pub appearance: Option<Appearance>,
 }
 
 fn default_version() -> String {
     "0.1.0".to_string()
 }
 
+/// Light/dark appearance classification for a theme variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Appearance {
+    Light,
+    Dark,
+}
+
+/// A theme's `extends` field: either one base theme name or several, merged in
+/// order (each entry overlaying the one before it) before the theme itself is
+/// overlaid on top.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Extends {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Extends {
+    /// The base theme names to merge, in application order
+    pub fn names(&self) -> Vec<String> {
+        match self {
+            Extends::One(name) => vec![name.clone()],
+            Extends::Many(names) => names.clone(),
+        }
+    }
+}
+
+/// A bundle of related theme variants (e.g. "dark", "light", "soft dark") shipped in one file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThemeFamily {
+    /// Name of the theme family
+    pub name: String,
+
+    /// Author of the theme family
+    #[serde(default)]
+    pub author: Option<String>,
+
+    /// Variants in this family
+    pub themes: Vec<Theme>,
+}
+
+impl ThemeFamily {
+    /// Load a theme family from a file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        match load_theme_file(&path)? {
+            LoadedThemeFile::Family(family) => Ok(family),
+            LoadedThemeFile::Single(theme) => {
+                Err(color_eyre::eyre::eyre!("'{}' is a single theme, not a theme family", theme.name))
+            }
+        }
+    }
+
+    /// Emit this type's JSON Schema as a pretty-printed string, for editor autocompletion
+    pub fn schema() -> String {
+        serde_json::to_string_pretty(&crate::validate::theme_family_schema()).unwrap_or_default()
+    }
+}
+
+/// Result of parsing a theme file, which may contain either a single theme or a family
+enum LoadedThemeFile {
+    Single(Theme),
+    Family(ThemeFamily),
+}
+
+/// Attribute every color/variable key a theme directly sets to that theme's name,
+/// as `"colors.accent"` / `"variables.gaps"` -> theme name
+fn theme_key_provenance(theme: &Theme) -> HashMap<String, String> {
+    let mut provenance = HashMap::new();
+    for key in theme.colors.keys() {
+        provenance.insert(format!("colors.{}", key), theme.name.clone());
+    }
+    for key in theme.variables.keys() {
+        provenance.insert(format!("variables.{}", key), theme.name.clone());
+    }
+    provenance
+}
+
+/// Parse a theme file, detecting whether it holds a single `Theme` or a `ThemeFamily`
+/// (a family is any document whose top-level table has a `themes` array).
+fn load_theme_file<P: AsRef<Path>>(path: P) -> Result<LoadedThemeFile> {
+    let path = path.as_ref();
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read theme file: {}", path.display()))?;
+
+    let extension = path.extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("");
+
+    match extension {
+        "toml" => {
+            let value: toml::Value = toml::from_str(&content)
+                .with_context(|| format!("Failed to parse TOML theme file: {}", path.display()))?;
+
+            if value.get("themes").map(|v| v.is_array()).unwrap_or(false) {
+                let family: ThemeFamily = value.try_into()
+                    .with_context(|| format!("Failed to parse TOML theme family: {}", path.display()))?;
+                Ok(LoadedThemeFile::Family(family))
+            } else {
+                let theme: Theme = value.try_into()
+                    .with_context(|| format!("Failed to parse TOML theme: {}", path.display()))?;
+                Ok(LoadedThemeFile::Single(theme))
+            }
+        },
+        "json" => {
+            let value: serde_json::Value = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse JSON theme file: {}", path.display()))?;
+
+            if value.get("themes").map(|v| v.is_array()).unwrap_or(false) {
+                let family: ThemeFamily = serde_json::from_value(value)
+                    .with_context(|| format!("Failed to parse JSON theme family: {}", path.display()))?;
+                Ok(LoadedThemeFile::Family(family))
+            } else {
+                let theme: Theme = serde_json::from_value(value)
+                    .with_context(|| format!("Failed to parse JSON theme: {}", path.display()))?;
+                Ok(LoadedThemeFile::Single(theme))
+            }
+        },
+        _ => {
+            Err(color_eyre::eyre::eyre!("Unsupported theme file format: {}", extension))
+        }
+    }
+}
+
 impl Theme {
     /// Create a new theme with the given name
     pub fn new(name: &str) -> Self {
@@ -57,31 +185,20 @@ impl Theme {
             colors: HashMap::new(),
             variables: HashMap::new(),
             metadata: HashMap::new(),
+            appearance: None,
         }
     }
-    
-    /// Load a theme from a file
+
+    /// Load a theme from a file. Fails if the file is a theme family; use
+    /// `ThemeFamily::from_file` or address a specific variant as `family/variant` instead.
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
         let path = path.as_ref();
-        let content = fs::read_to_string(path)
-            .with_context(|| format!("Failed to read theme file: {}", path.display()))?;
-        
-        let extension = path.extension()
-            .and_then(|ext| ext.to_str())
-            .unwrap_or("");
-        
-        match extension {
-            "toml" => {
-                toml::from_str(&content)
-                    .with_context(|| format!("Failed to parse TOML theme file: {}", path.display()))
-            },
-            "json" => {
-                serde_json::from_str(&content)
-                    .with_context(|| format!("Failed to parse JSON theme file: {}", path.display()))
-            },
-            _ => {
-                Err(color_eyre::eyre::eyre!("Unsupported theme file format: {}", extension))
-            }
+        match load_theme_file(path)? {
+            LoadedThemeFile::Single(theme) => Ok(theme),
+            LoadedThemeFile::Family(family) => Err(color_eyre::eyre::eyre!(
+                "'{}' is a theme family ('{}'); select a variant with '{}/<variant>'",
+                path.display(), family.name, family.name
+            )),
         }
     }
     
@@ -124,6 +241,24 @@ impl Theme {
         }
     }
     
+    /// Emit this type's JSON Schema as a pretty-printed string, for editor autocompletion
+    pub fn schema() -> String {
+        serde_json::to_string_pretty(&crate::validate::theme_schema()).unwrap_or_default()
+    }
+
+    /// The built-in default theme, for environments with no theme files installed
+    pub fn default_theme() -> Theme {
+        builtin::theme("tokyonight").expect("built-in default theme should always parse")
+    }
+
+    /// Parse and validate a theme file, collecting every unknown key and missing
+    /// `name` rather than failing on the first one; of type mismatches, only the first
+    /// is reported, and only when no other problem was found. In `strict` mode,
+    /// unrecognized keys are reported as errors rather than silently tolerated.
+    pub fn validate_file<P: AsRef<Path>>(path: P, strict: bool) -> Result<crate::validate::ValidationReport> {
+        crate::validate::validate_theme_file(path, strict)
+    }
+
     /// Get a color value by name
     pub fn get_color(&self, name: &str) -> Option<&String> {
         self.colors.get(name)
@@ -142,8 +277,52 @@ pub enum ThemeFormat {
     Json,
 }
 
+/// Typed errors from theme lookup, matchable by callers instead of string-sniffing an
+/// `eyre!` report (mirrors `PluginError` in `plugins.rs`)
+#[derive(Debug, thiserror::Error)]
+pub enum ThemeError {
+    /// No theme (built-in or user) is registered under this name
+    #[error("Theme not found: {0}")]
+    NotFound(String),
+
+    /// No theme family file is registered under this name
+    #[error("Theme family not found: {0}")]
+    FamilyNotFound(String),
+
+    /// `family` has no variant named `variant`
+    #[error("Variant '{variant}' not found in theme family '{family}'")]
+    VariantNotFound { variant: String, family: String },
+}
+
+/// The chain of theme names currently being resolved through `extends`, used to detect
+/// and report inheritance cycles. `order` preserves resolution order so a reported cycle
+/// reads as the actual chain (`a -> b -> a`) rather than whatever order a `HashSet`
+/// happens to iterate in; `seen` mirrors it for O(1) membership checks.
+#[derive(Debug, Clone, Default)]
+struct InheritanceStack {
+    order: Vec<String>,
+    seen: HashSet<String>,
+}
+
+impl InheritanceStack {
+    fn contains(&self, name: &str) -> bool {
+        self.seen.contains(name)
+    }
+
+    fn push(&mut self, name: String) {
+        self.seen.insert(name.clone());
+        self.order.push(name);
+    }
+
+    fn pop(&mut self) {
+        if let Some(name) = self.order.pop() {
+            self.seen.remove(&name);
+        }
+    }
+}
+
 /// Theme loader for managing theme file loading
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ThemeLoader {
     /// Directories to search for themes
     theme_dirs: Vec<PathBuf>,
@@ -162,31 +341,147 @@ impl ThemeLoader {
         self.theme_dirs.push(path.as_ref().to_path_buf());
         self
     }
-    
-    /// Load a theme by name
+
+    /// Directories this loader searches for themes
+    pub fn theme_dirs(&self) -> &[PathBuf] {
+        &self.theme_dirs
+    }
+
+    /// Load a theme by name, resolving any `extends` inheritance chain
     pub fn load_theme(&self, name: &str) -> Result<Theme> {
+        let mut stack = InheritanceStack::default();
+        self.load_theme_resolved(name, &mut stack).map(|(theme, _)| theme)
+    }
+
+    /// Load a theme by name, resolving its `extends` chain, and also return which
+    /// ancestor theme last set each key, as `"colors.accent"` / `"variables.gaps"`
+    /// mapped to the theme name that set it. Used by `theme show` to explain
+    /// where each value in the resolved theme came from.
+    pub fn load_theme_with_provenance(&self, name: &str) -> Result<(Theme, HashMap<String, String>)> {
+        let mut stack = InheritanceStack::default();
+        self.load_theme_resolved(name, &mut stack)
+    }
+
+    /// Load a theme by name without resolving `extends`, overlaying any user
+    /// theme file of the same name on top of the built-in default of that name
+    /// (if any). Accepts either a plain theme name or a `family/variant` name
+    /// addressing one variant of a theme family file.
+    fn load_theme_raw(&self, name: &str) -> Result<Theme> {
+        if let Some((family_name, variant_name)) = name.split_once('/') {
+            return self.load_family_variant(family_name, variant_name);
+        }
+
+        let user_theme = self.find_user_theme(name)?;
+
+        match (builtin::theme(name), user_theme) {
+            (Some(mut base), Some(user)) => {
+                base.merge(&user);
+                base.name = user.name.clone();
+                Ok(base)
+            },
+            (Some(base), None) => Ok(base),
+            (None, Some(user)) => Ok(user),
+            (None, None) => Err(ThemeError::NotFound(name.to_string()).into()),
+        }
+    }
+
+    /// Look for a user-authored theme file by name across the configured theme
+    /// directories, without consulting the built-in defaults
+    fn find_user_theme(&self, name: &str) -> Result<Option<Theme>> {
         for dir in &self.theme_dirs {
             // Try different extensions
             for ext in &["toml", "json"] {
                 let path = dir.join(format!("{}.{}", name, ext));
                 if path.exists() {
-                    return Theme::from_file(path);
+                    return match load_theme_file(&path)? {
+                        LoadedThemeFile::Single(theme) => Ok(Some(theme)),
+                        LoadedThemeFile::Family(family) => {
+                            let theme = family.themes.into_iter().next()
+                                .ok_or_else(|| color_eyre::eyre::eyre!("Theme family '{}' has no variants", family.name))?;
+                            Ok(Some(theme))
+                        },
+                    };
                 }
             }
-            
+
             // Look in subdirectories
             let subdir_path = dir.join(name);
             if subdir_path.exists() && subdir_path.is_dir() {
                 for ext in &["toml", "json"] {
                     let path = subdir_path.join(format!("theme.{}", ext));
                     if path.exists() {
-                        return Theme::from_file(path);
+                        return Ok(Some(Theme::from_file(path)?));
                     }
                 }
             }
         }
-        
-        Err(color_eyre::eyre::eyre!("Theme not found: {}", name))
+
+        Ok(None)
+    }
+
+    /// Load a theme family by name
+    pub fn load_family(&self, name: &str) -> Result<ThemeFamily> {
+        for dir in &self.theme_dirs {
+            for ext in &["toml", "json"] {
+                let path = dir.join(format!("{}.{}", name, ext));
+                if path.exists() {
+                    return ThemeFamily::from_file(path);
+                }
+            }
+        }
+
+        Err(ThemeError::FamilyNotFound(name.to_string()).into())
+    }
+
+    /// Load a single variant out of a theme family file addressed as `family/variant`
+    fn load_family_variant(&self, family_name: &str, variant_name: &str) -> Result<Theme> {
+        let family = self.load_family(family_name)?;
+        family.themes.into_iter()
+            .find(|theme| theme.name == variant_name)
+            .ok_or_else(|| ThemeError::VariantNotFound { variant: variant_name.to_string(), family: family_name.to_string() }.into())
+    }
+
+    /// Load a theme and recursively resolve its `extends` chain, guarding against cycles.
+    /// Also returns which ancestor theme each key in the result was last set by.
+    fn load_theme_resolved(&self, name: &str, stack: &mut InheritanceStack) -> Result<(Theme, HashMap<String, String>)> {
+        if stack.contains(name) {
+            let chain: Vec<&str> = stack.order.iter().map(|s| s.as_str()).chain(std::iter::once(name)).collect();
+            return Err(color_eyre::eyre::eyre!("circular theme inheritance: {}", chain.join(" -> ")));
+        }
+        stack.push(name.to_string());
+
+        let child = self.load_theme_raw(name)?;
+
+        let resolved = if let Some(extends) = child.extends.clone() {
+            let mut merged: Option<(Theme, HashMap<String, String>)> = None;
+            for base_name in extends.names() {
+                let next = self.load_theme_resolved(&base_name, stack)?;
+                merged = Some(match merged {
+                    None => next,
+                    Some((mut acc_theme, mut acc_provenance)) => {
+                        acc_theme.merge(&next.0);
+                        acc_provenance.extend(next.1);
+                        (acc_theme, acc_provenance)
+                    },
+                });
+            }
+
+            let (mut base_theme, mut provenance) = merged
+                .ok_or_else(|| color_eyre::eyre::eyre!("'{}' has an empty extends list", name))?;
+
+            base_theme.merge(&child);
+            provenance.extend(theme_key_provenance(&child));
+            // The merged theme keeps the child's own identity, not the base's
+            base_theme.name = child.name.clone();
+            base_theme.extends = child.extends.clone();
+            (base_theme, provenance)
+        } else {
+            let provenance = theme_key_provenance(&child);
+            (child, provenance)
+        };
+
+        stack.pop();
+        Ok(resolved)
     }
     
     /// List all available themes
@@ -209,7 +504,16 @@ impl ThemeLoader {
                     if ext == "toml" || ext == "json" {
                         if let Some(name) = path.file_stem().and_then(|name| name.to_str()) {
                             if name != "theme" {
-                                themes.push(name.to_string());
+                                // A plain-named file may be a single theme or a family;
+                                // families are listed as one "family/variant" entry per variant
+                                match load_theme_file(path) {
+                                    Ok(LoadedThemeFile::Family(family)) => {
+                                        for variant in &family.themes {
+                                            themes.push(format!("{}/{}", name, variant.name));
+                                        }
+                                    },
+                                    _ => themes.push(name.to_string()),
+                                }
                             } else if let Some(parent_dir) = path.parent() {
                                 if let Some(parent_name) = parent_dir.file_name().and_then(|name| name.to_str()) {
                                     if parent_name != dir.file_name().and_then(|name| name.to_str()).unwrap_or("") {
@@ -223,6 +527,9 @@ impl ThemeLoader {
             }
         }
         
+        // Built-in themes are always available, even with no theme directory
+        themes.extend(builtin::names());
+
         // Remove duplicates
         themes.sort();
         themes.dedup();
@@ -234,34 +541,42 @@ impl ThemeLoader {
 impl Default for ThemeLoader {
     fn default() -> Self {
         let mut loader = ThemeLoader::new();
-        
-        // Add default theme directories
-        if let Some(config_dir) = dirs::config_dir() {
-            loader.add_theme_dir(config_dir.join("hyprsupreme/themes"));
-        }
-        
+
+        // Add default theme directories, in XDG resolution order
+        loader.add_theme_dir(crate::xdg::Dirs::resolve().theme_dir());
+
         if let Some(data_dir) = dirs::data_dir() {
             loader.add_theme_dir(data_dir.join("hyprsupreme/themes"));
         }
-        
+
         // Add local themes directory
         loader.add_theme_dir("./themes");
-        
+
         loader
     }
 }
 
+/// Events emitted by `ThemeManager::watch` as theme files change on disk
+#[derive(Debug, Clone)]
+pub enum ThemeEvent {
+    /// A theme file was created, modified, or removed; its cache entry was invalidated
+    ThemeChanged(String),
+
+    /// The currently active theme was re-resolved and swapped in
+    ActiveThemeChanged(Theme),
+}
+
 /// Manager for handling themes
 #[derive(Debug)]
 pub struct ThemeManager {
     /// Theme loader
     loader: ThemeLoader,
-    
+
     /// Currently active theme
     active_theme: Arc<RwLock<Option<Theme>>>,
-    
-    /// Cache of loaded themes
-    theme_cache: HashMap<String, Theme>,
+
+    /// Cache of loaded themes, shared with the background watcher thread
+    theme_cache: Arc<RwLock<HashMap<String, Theme>>>,
 }
 
 impl ThemeManager {
@@ -270,27 +585,122 @@ impl ThemeManager {
         ThemeManager {
             loader: ThemeLoader::default(),
             active_theme: Arc::new(RwLock::new(None)),
-            theme_cache: HashMap::new(),
+            theme_cache: Arc::new(RwLock::new(HashMap::new())),
         }
     }
-    
+
     /// Load and set the active theme
     pub fn set_theme(&mut self, name: &str) -> Result<()> {
-        let theme = if let Some(cached) = self.theme_cache.get(name) {
-            cached.clone()
+        let cached = self.theme_cache.read().unwrap().get(name).cloned();
+        let theme = if let Some(cached) = cached {
+            cached
         } else {
             let theme = self.loader.load_theme(name)?;
-            self.theme_cache.insert(name.to_string(), theme.clone());
+            self.theme_cache.write().unwrap().insert(name.to_string(), theme.clone());
             theme
         };
-        
+
         // Set the active theme
         let mut active = self.active_theme.write().unwrap();
         *active = Some(theme);
-        
+
         Ok(())
     }
+
+    /// Watch every theme directory for changes and stream events as files are
+    /// created, modified, or removed. A changed file's stem is used as the theme name:
+    /// its cache entry under that name is invalidated, and if it equals the active
+    /// theme's `name`, the active theme is re-resolved (honoring inheritance) and
+    /// swapped in automatically. A variant selected from a theme family file is not
+    /// matched this way (nor is its `family/variant` cache entry invalidated), and a
+    /// change to a base theme the active theme `extends` does not re-resolve it.
+    pub fn watch(&self) -> Result<std::sync::mpsc::Receiver<ThemeEvent>> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let loader = self.loader.clone();
+        let active_theme = Arc::clone(&self.active_theme);
+        let theme_cache = Arc::clone(&self.theme_cache);
+
+        let (fs_tx, fs_rx) = std::sync::mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let _ = fs_tx.send(event);
+        }).with_context(|| "Failed to create theme filesystem watcher")?;
+
+        for dir in loader.theme_dirs() {
+            if dir.exists() {
+                notify::Watcher::watch(&mut watcher, dir, notify::RecursiveMode::Recursive)
+                    .with_context(|| format!("Failed to watch theme directory: {}", dir.display()))?;
+            }
+        }
+
+        std::thread::spawn(move || {
+            // Keep the watcher alive for as long as this thread runs
+            let _watcher = watcher;
+
+            for event in fs_rx {
+                let event = match event {
+                    Ok(event) => event,
+                    Err(err) => {
+                        tracing::warn!("Theme watcher error: {}", err);
+                        continue;
+                    }
+                };
+
+                if !matches!(event.kind, notify::EventKind::Create(_) | notify::EventKind::Modify(_) | notify::EventKind::Remove(_)) {
+                    continue;
+                }
+
+                for path in &event.paths {
+                    let is_theme_file = path.extension()
+                        .and_then(|ext| ext.to_str())
+                        .map(|ext| ext == "toml" || ext == "json")
+                        .unwrap_or(false);
+
+                    if !is_theme_file {
+                        continue;
+                    }
+
+                    let name = match path.file_stem().and_then(|stem| stem.to_str()) {
+                        Some(name) => name.to_string(),
+                        None => continue,
+                    };
+
+                    theme_cache.write().unwrap().remove(&name);
+                    if tx.send(ThemeEvent::ThemeChanged(name.clone())).is_err() {
+                        return;
+                    }
+
+                    let active_name = active_theme.read().unwrap().as_ref().map(|theme| theme.name.clone());
+                    if active_name.as_deref() != Some(name.as_str()) {
+                        continue;
+                    }
+
+                    match loader.load_theme(&name) {
+                        Ok(reloaded) => {
+                            *active_theme.write().unwrap() = Some(reloaded.clone());
+                            if tx.send(ThemeEvent::ActiveThemeChanged(reloaded)).is_err() {
+                                return;
+                            }
+                        },
+                        Err(err) => tracing::warn!("Failed to re-resolve active theme '{}': {}", name, err),
+                    }
+                }
+            }
+        });
+
+        Ok(rx)
+    }
     
+    /// Set the active theme to the variant of a theme family matching the given appearance
+    pub fn set_theme_for_appearance(&mut self, family_name: &str, appearance: Appearance) -> Result<()> {
+        let family = self.loader.load_family(family_name)?;
+        let variant = family.themes.iter()
+            .find(|theme| theme.appearance == Some(appearance))
+            .ok_or_else(|| color_eyre::eyre::eyre!("No {:?} variant found in theme family '{}'", appearance, family_name))?;
+
+        let qualified_name = format!("{}/{}", family_name, variant.name);
+        self.set_theme(&qualified_name)
+    }
+
     /// Get the currently active theme
     pub fn get_active_theme(&self) -> Option<Theme> {
         let active = self.active_theme.read().unwrap();
@@ -340,54 +750,61 @@ impl ThemeManager {
             Err(color_eyre::eyre::eyre!("No active theme"))
         }
     }
-    
+
+    /// Get the light/dark appearance of the currently active theme
+    pub fn get_theme_appearance(&self) -> Result<Appearance> {
+        let active = self.active_theme.read().unwrap();
+        active.as_ref()
+            .ok_or_else(|| color_eyre::eyre::eyre!("No active theme"))?
+            .appearance
+            .ok_or_else(|| color_eyre::eyre::eyre!("Active theme has no appearance set"))
+    }
+
     /// Get list of all themes
     pub fn get_themes(&self) -> Vec<String> {
         self.list_themes()
     }
-    
-    /// Reload the current theme
+
+    /// Reload the current theme, rescanning theme directories so newly dropped-in
+    /// theme files (and theme family variants) are picked up without a rebuild
     pub fn reload_theme(&mut self) -> Result<()> {
         let active = self.active_theme.read().unwrap();
         if let Some(theme) = active.as_ref() {
             let theme_name = theme.name.clone();
             drop(active); // Release the read lock
-            
-            // Clear cache and reload
-            self.theme_cache.remove(&theme_name);
+
+            // Drop the whole cache, not just this entry, so new/changed theme
+            // files elsewhere in the directory are reflected too
+            self.theme_cache.write().unwrap().clear();
             self.set_theme(&theme_name)
         } else {
             Err(color_eyre::eyre::eyre!("No active theme to reload"))
         }
     }
     
-    /// Save a theme to disk
+    /// Save a theme to disk, under the resolved XDG theme directory
     pub fn save_theme(&mut self, theme: &Theme, format: ThemeFormat) -> Result<PathBuf> {
-        if let Some(config_dir) = dirs::config_dir() {
-            let theme_dir = config_dir.join("hyprsupreme/themes");
-            
-            // Create directory if it doesn't exist
-            if !theme_dir.exists() {
-                fs::create_dir_all(&theme_dir)
-                    .with_context(|| format!("Failed to create theme directory: {}", theme_dir.display()))?;
-            }
-            
-            // Determine file extension
-            let ext = match format {
-                ThemeFormat::Toml => "toml",
-                ThemeFormat::Json => "json",
-            };
-            
-            let path = theme_dir.join(format!("{}.{}", theme.name, ext));
-            theme.save_to_file(&path, format)?;
-            
-            // Add to cache
-            self.theme_cache.insert(theme.name.clone(), theme.clone());
-            
-            Ok(path)
-        } else {
-            Err(color_eyre::eyre::eyre!("Could not determine config directory"))
+        let theme_dir = crate::xdg::Dirs::resolve().theme_dir();
+
+        // Create directory if it doesn't exist
+        if !theme_dir.exists() {
+            fs::create_dir_all(&theme_dir)
+                .with_context(|| format!("Failed to create theme directory: {}", theme_dir.display()))?;
         }
+
+        // Determine file extension
+        let ext = match format {
+            ThemeFormat::Toml => "toml",
+            ThemeFormat::Json => "json",
+        };
+
+        let path = theme_dir.join(format!("{}.{}", theme.name, ext));
+        theme.save_to_file(&path, format)?;
+
+        // Add to cache
+        self.theme_cache.write().unwrap().insert(theme.name.clone(), theme.clone());
+
+        Ok(path)
     }
 }
 
@@ -396,3 +813,67 @@ impl Default for ThemeManager {
         Self::new()
     }
 }
+
+/// Themes compiled directly into the binary so a fresh install has working
+/// themes to apply even with an empty theme directory. Looked up by name
+/// alongside the user's theme directories; when both define the same name,
+/// the user's file is merged on top so individual keys can be overridden
+/// without copying the whole theme.
+mod builtin {
+    use super::Theme;
+
+    const TOKYONIGHT: &str = r#"
+        name = "tokyonight"
+        author = "HyprSupreme Builder"
+        description = "A clean dark theme with blue accents"
+        appearance = "dark"
+
+        [colors]
+        background = "#1a1b26"
+        foreground = "#c0caf5"
+        accent = "#7aa2f7"
+        red = "#f7768e"
+        green = "#9ece6a"
+        yellow = "#e0af68"
+
+        [variables]
+        border_radius = "8"
+        gaps = "6"
+    "#;
+
+    const NORD_LIGHT: &str = r#"
+        name = "nord-light"
+        author = "HyprSupreme Builder"
+        description = "A soft light theme"
+        appearance = "light"
+
+        [colors]
+        background = "#eceff4"
+        foreground = "#2e3440"
+        accent = "#5e81ac"
+        red = "#bf616a"
+        green = "#a3be8c"
+        yellow = "#ebcb8b"
+
+        [variables]
+        border_radius = "8"
+        gaps = "6"
+    "#;
+
+    const ALL: &[&str] = &[TOKYONIGHT, NORD_LIGHT];
+
+    /// Look up a built-in theme by name
+    pub fn theme(name: &str) -> Option<Theme> {
+        ALL.iter()
+            .filter_map(|raw| toml::from_str::<Theme>(raw).ok())
+            .find(|theme| theme.name == name)
+    }
+
+    /// Names of every built-in theme, for listing alongside user themes
+    pub fn names() -> Vec<String> {
+        ALL.iter()
+            .filter_map(|raw| toml::from_str::<Theme>(raw).ok())
+            .map(|theme| theme.name)
+            .collect()
+    }
+}
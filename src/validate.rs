@@ -0,0 +1,331 @@
+use serde_json::json;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use color_eyre::{eyre::Context, Result};
+
+use crate::config::Config;
+use crate::themes::{Theme, ThemeFamily};
+
+/// A single problem found while validating a document, with enough context to locate it
+#[derive(Debug, Clone)]
+pub struct ValidationError {
+    /// Dotted path to the offending key (e.g. `hyprland.keybindings[2].key`)
+    pub path: String,
+
+    /// Human-readable description of the problem
+    pub message: String,
+
+    /// 1-indexed source line the problem was found at, when the parser that raised it
+    /// reports a byte span (syntax errors do; errors raised against an already-parsed
+    /// `toml::Value`/`serde_json::Value` tree, like missing-field and type-mismatch
+    /// checks, generally don't and leave this `None`)
+    pub line: Option<usize>,
+
+    /// 1-indexed source column the problem was found at; see `line`
+    pub column: Option<usize>,
+}
+
+impl ValidationError {
+    /// A validation error with no known source location
+    fn unlocated(path: impl Into<String>, message: impl Into<String>) -> Self {
+        ValidationError { path: path.into(), message: message.into(), line: None, column: None }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "{} ({}:{}): {}", self.path, line, column, self.message),
+            _ => write!(f, "{}: {}", self.path, self.message),
+        }
+    }
+}
+
+/// Convert a 0-indexed byte offset into `content` to a 1-indexed (line, column) pair
+fn byte_offset_to_line_col(content: &str, offset: usize) -> (usize, usize) {
+    let mut line = 1;
+    let mut column = 1;
+    for ch in content[..offset.min(content.len())].chars() {
+        if ch == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+    }
+    (line, column)
+}
+
+/// The result of validating a single file: every problem found, not just the first
+#[derive(Debug, Clone, Default)]
+pub struct ValidationReport {
+    /// File the report is about, for summary output
+    pub file: String,
+
+    /// All problems found in the file
+    pub errors: Vec<ValidationError>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// One-line summary like `3 errors in hyprsupreme.toml`
+    pub fn summary(&self) -> String {
+        format!("{} error{} in {}", self.errors.len(), if self.errors.len() == 1 { "" } else { "s" }, self.file)
+    }
+}
+
+/// Generate a JSON Schema describing a `Config` document
+pub fn config_schema() -> serde_json::Value {
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "HyprSupreme Config",
+        "type": "object",
+        "properties": {
+            "metadata": { "type": "object" },
+            "variables": { "type": "object", "additionalProperties": { "type": "string" } },
+            "profiles": { "type": "object" },
+            "default_profile": { "type": "string" },
+            "imports": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["path"],
+                    "properties": {
+                        "path": { "type": "string" },
+                        "merge": { "type": "boolean" },
+                        "strategy": { "type": "string", "enum": ["deep", "replace", "append-arrays"] }
+                    }
+                }
+            },
+            "hyprland": {
+                "type": "object",
+                "properties": {
+                    "config_path": { "type": "string" },
+                    "modules": { "type": "array" },
+                    "theme": { "type": "object", "additionalProperties": { "type": "string" } },
+                    "keybindings": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "required": ["modifiers", "key", "command"],
+                            "properties": {
+                                "modifiers": { "type": "array", "items": { "type": "string" } },
+                                "key": { "type": "string" },
+                                "command": { "type": "string" },
+                                "description": { "type": "string" }
+                            }
+                        }
+                    },
+                    "autostart": { "type": "array" }
+                }
+            }
+        }
+    })
+}
+
+/// Generate a JSON Schema describing a `Theme` document
+pub fn theme_schema() -> serde_json::Value {
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "HyprSupreme Theme",
+        "type": "object",
+        "required": ["name"],
+        "properties": {
+            "name": { "type": "string" },
+            "author": { "type": "string" },
+            "description": { "type": "string" },
+            "version": { "type": "string" },
+            "extends": {
+                "oneOf": [
+                    { "type": "string" },
+                    { "type": "array", "items": { "type": "string" } }
+                ]
+            },
+            "appearance": { "type": "string", "enum": ["light", "dark"] },
+            "colors": { "type": "object", "additionalProperties": { "type": "string" } },
+            "variables": { "type": "object", "additionalProperties": { "type": "string" } },
+            "metadata": { "type": "object", "additionalProperties": { "type": "string" } }
+        }
+    })
+}
+
+/// Generate a JSON Schema describing a `ThemeFamily` document
+pub fn theme_family_schema() -> serde_json::Value {
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "HyprSupreme Theme Family",
+        "type": "object",
+        "required": ["name", "themes"],
+        "properties": {
+            "name": { "type": "string" },
+            "author": { "type": "string" },
+            "themes": { "type": "array", "items": theme_schema() }
+        }
+    })
+}
+
+/// Parse a config file and report every structural problem found, instead of failing
+/// on the first one the way `Config::from_file` does. In `strict` mode, keys not
+/// known to the schema are reported as errors; otherwise they are ignored, the way
+/// most TOML tools tolerate forward-compatible extra fields.
+pub fn validate_config_file<P: AsRef<Path>>(path: P, strict: bool) -> Result<ValidationReport> {
+    let path = path.as_ref();
+    let file = path.file_name().and_then(|n| n.to_str()).unwrap_or("<config>").to_string();
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+
+    let mut errors = Vec::new();
+
+    let value: toml::Value = match toml::from_str(&content) {
+        Ok(value) => value,
+        Err(err) => {
+            errors.push(toml_error("<root>", &content, &err));
+            return Ok(ValidationReport { file, errors });
+        }
+    };
+
+    if strict {
+        check_known_keys(&value, "", &["metadata", "variables", "profiles", "default_profile", "imports", "hyprland"], &mut errors);
+    }
+
+    if let Some(imports) = value.get("imports").and_then(|v| v.as_array()) {
+        for (index, import) in imports.iter().enumerate() {
+            let prefix = format!("imports[{}]", index);
+            if import.get("path").is_none() {
+                errors.push(ValidationError::unlocated(prefix.clone(), "missing required field `path`"));
+            }
+        }
+    }
+
+    if let Some(keybindings) = value.get("hyprland").and_then(|h| h.get("keybindings")).and_then(|v| v.as_array()) {
+        for (index, binding) in keybindings.iter().enumerate() {
+            let prefix = format!("hyprland.keybindings[{}]", index);
+            for field in ["modifiers", "key", "command"] {
+                if binding.get(field).is_none() {
+                    errors.push(ValidationError::unlocated(prefix.clone(), format!("missing required field `{}`", field)));
+                }
+            }
+        }
+    }
+
+    // Attempting a full deserialization surfaces any remaining type mismatches. This
+    // only reports the first one serde encounters -- collecting every mismatch in a
+    // single pass would need a custom `Deserializer` that keeps going past errors
+    // instead of short-circuiting, which toml/serde don't support out of the box. Skip
+    // it once the checks above already found something: serde would almost always just
+    // re-report the same missing field as its own first error, double-counting it.
+    if errors.is_empty() {
+        if let Err(err) = value.clone().try_into::<Config>() {
+            errors.push(toml_error("<root>", &content, &err));
+        }
+    }
+
+    Ok(ValidationReport { file, errors })
+}
+
+/// Parse a theme (or theme family) file and report every structural problem found.
+/// Covers both `ThemeFormat::Toml` and `ThemeFormat::Json` inputs against the same schema.
+pub fn validate_theme_file<P: AsRef<Path>>(path: P, strict: bool) -> Result<ValidationReport> {
+    let path = path.as_ref();
+    let file = path.file_name().and_then(|n| n.to_str()).unwrap_or("<theme>").to_string();
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read theme file: {}", path.display()))?;
+
+    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
+    let mut errors = Vec::new();
+
+    let value: serde_json::Value = match extension {
+        "toml" => match content.parse::<toml::Value>() {
+            Ok(value) => serde_json::to_value(value).unwrap_or(serde_json::Value::Null),
+            Err(err) => {
+                errors.push(toml_error("<root>", &content, &err));
+                return Ok(ValidationReport { file, errors });
+            }
+        },
+        "json" => match serde_json::from_str(&content) {
+            Ok(value) => value,
+            Err(err) => {
+                errors.push(json_error("<root>", &err));
+                return Ok(ValidationReport { file, errors });
+            }
+        },
+        other => {
+            errors.push(ValidationError::unlocated("<root>", format!("unsupported theme file format: {}", other)));
+            return Ok(ValidationReport { file, errors });
+        }
+    };
+
+    let is_family = value.get("themes").map(|v| v.is_array()).unwrap_or(false);
+
+    if strict {
+        let known: &[&str] = if is_family { &["name", "author", "themes"] } else { THEME_KNOWN_KEYS };
+        check_known_json_keys(&value, "", known, &mut errors);
+    }
+
+    if value.get("name").is_none() {
+        errors.push(ValidationError::unlocated("<root>", "missing required field `name`"));
+    }
+
+    // Type mismatches, like the TOML path above, only report the first one serde
+    // finds, and only once the checks above have confirmed they're not about to
+    // re-report the same missing field as their own first error.
+    if errors.is_empty() {
+        let result = if is_family {
+            serde_json::from_value::<ThemeFamily>(value).map(|_| ())
+        } else {
+            serde_json::from_value::<Theme>(value).map(|_| ())
+        };
+        if let Err(err) = result {
+            errors.push(json_error("<root>", &err));
+        }
+    }
+
+    Ok(ValidationReport { file, errors })
+}
+
+/// Build a `ValidationError` from a `toml::de::Error`, resolving its byte span (if it
+/// has one) against `content` into a line/column
+fn toml_error(path: impl Into<String>, content: &str, err: &toml::de::Error) -> ValidationError {
+    let (line, column) = err.span()
+        .map(|span| byte_offset_to_line_col(content, span.start))
+        .unzip();
+    ValidationError { path: path.into(), message: err.to_string(), line, column }
+}
+
+/// Build a `ValidationError` from a `serde_json::Error`. `line()`/`column()` only
+/// report a real position for errors raised while streaming input; errors raised
+/// against an already-parsed `Value` (as `from_value` does) report `0`, which is
+/// surfaced here as "unknown" rather than a bogus `0:0`.
+fn json_error(path: impl Into<String>, err: &serde_json::Error) -> ValidationError {
+    let (line, column) = if err.line() == 0 { (None, None) } else { (Some(err.line()), Some(err.column())) };
+    ValidationError { path: path.into(), message: err.to_string(), line, column }
+}
+
+const THEME_KNOWN_KEYS: &[&str] = &["name", "author", "description", "version", "extends", "appearance", "colors", "variables", "metadata"];
+
+fn check_known_keys(value: &toml::Value, prefix: &str, known: &[&str], errors: &mut Vec<ValidationError>) {
+    if let Some(table) = value.as_table() {
+        for key in table.keys() {
+            if !known.contains(&key.as_str()) {
+                let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                errors.push(ValidationError::unlocated(path, "unknown key"));
+            }
+        }
+    }
+}
+
+fn check_known_json_keys(value: &serde_json::Value, prefix: &str, known: &[&str], errors: &mut Vec<ValidationError>) {
+    if let Some(table) = value.as_object() {
+        for key in table.keys() {
+            if !known.contains(&key.as_str()) {
+                let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                errors.push(ValidationError::unlocated(path, "unknown key"));
+            }
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::path::PathBuf;
+
+/// System-wide config directory consulted when no per-user config exists yet,
+/// mirroring how most Linux daemons fall back to `/etc/<name>`.
+const SYSTEM_CONFIG_DIR: &str = "/etc/hyprsupreme";
+
+/// Resolved set of directories hyprsupreme uses, following the XDG Base
+/// Directory spec (`dirs::config_dir()`/`data_dir()`/`cache_dir()` already honor
+/// `$XDG_CONFIG_HOME`/`$XDG_DATA_HOME`/`$XDG_CACHE_HOME`, falling back to the
+/// `~/.config`, `~/.local/share`, `~/.cache` defaults).
+#[derive(Debug, Clone)]
+pub struct Dirs {
+    /// Directory holding `hyprsupreme.toml`, with `themes/` and `plugins/` beneath it
+    pub config_dir: PathBuf,
+
+    /// Directory for persistent application data
+    pub data_dir: PathBuf,
+
+    /// Directory for disposable cache and log output
+    pub cache_dir: PathBuf,
+}
+
+impl Dirs {
+    /// Resolve the directories hyprsupreme reads and writes by default: the
+    /// user's XDG config dir if it already holds a `hyprsupreme` directory, else
+    /// the system-wide config dir if that exists, else the user dir anyway so
+    /// `Init` has somewhere to create one.
+    pub fn resolve() -> Self {
+        let user_config_dir = dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("hyprsupreme");
+        let system_config_dir = PathBuf::from(SYSTEM_CONFIG_DIR);
+
+        let config_dir = if user_config_dir.exists() {
+            user_config_dir
+        } else if system_config_dir.exists() {
+            system_config_dir
+        } else {
+            user_config_dir
+        };
+
+        let data_dir = dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("hyprsupreme");
+        let cache_dir = dirs::cache_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("hyprsupreme");
+
+        Dirs { config_dir, data_dir, cache_dir }
+    }
+
+    /// Path to the default config file within the resolved config directory
+    pub fn config_file(&self) -> PathBuf {
+        self.config_dir.join("hyprsupreme.toml")
+    }
+
+    /// Theme directory beneath the resolved config directory
+    pub fn theme_dir(&self) -> PathBuf {
+        self.config_dir.join("themes")
+    }
+
+    /// Plugin directory beneath the resolved config directory
+    pub fn plugin_dir(&self) -> PathBuf {
+        self.config_dir.join("plugins")
+    }
+}
+
+impl Default for Dirs {
+    fn default() -> Self {
+        Self::resolve()
+    }
+}